@@ -0,0 +1,88 @@
+use common::diagnostic::Diagnostic;
+use common::Solution;
+use std::collections::HashSet;
+use std::error::Error;
+
+pub struct Mapscan<I, St, F> {
+    state: St,
+    iter: I,
+    f: F,
+}
+
+impl<I, St, F> Mapscan<I, St, F> {
+    pub fn new(iter: I, state: St, f: F) -> Self {
+        Self { iter, state, f }
+    }
+}
+
+impl<I, St, F, O> Iterator for Mapscan<I, St, F>
+where
+    I: Iterator,
+    F: FnMut(&mut St, I::Item) -> O,
+{
+    type Item = O;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|item| (self.f)(&mut self.state, item))
+    }
+}
+
+trait IterPlus: Iterator {
+    fn map_scan<St, F, O>(self, state: St, f: F) -> Mapscan<Self, St, F>
+    where
+        Self: Sized,
+        F: FnMut(&mut St, Self::Item) -> O,
+    {
+        Mapscan::new(self, state, f)
+    }
+}
+
+impl<T: ?Sized> IterPlus for T where T: Iterator {}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 1;
+
+    type Input<'a> = Vec<i32>;
+    type One = i32;
+    type Two = i32;
+
+    fn parse(input: &str) -> Result<Vec<i32>, Box<dyn Error>> {
+        let freqs_change = input
+            .lines()
+            .map(|line| {
+                let line = line.trim();
+                line.parse().map_err(|e| {
+                    Diagnostic::new(input, line, format!("a frequency change like +7 ({})", e))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(freqs_change)
+    }
+
+    /// Final frequence
+    fn part_one(freqs_change: &Vec<i32>) -> Result<i32, Box<dyn Error>> {
+        Ok(freqs_change.iter().sum())
+    }
+
+    /// First repeated frequence
+    fn part_two(freqs_change: &Vec<i32>) -> Result<i32, Box<dyn Error>> {
+        let mut set = HashSet::new();
+        let first_repeat = Some(0)
+            .into_iter()
+            .chain(
+                freqs_change
+                    .iter()
+                    .cycle()
+                    .map_scan(0, |freq, freq_change| {
+                        *freq += freq_change;
+                        *freq
+                    }),
+            )
+            .find_map(|item| set.replace(item));
+
+        Ok(first_repeat.unwrap_or(0))
+    }
+}
@@ -0,0 +1,132 @@
+use common::diagnostic::Diagnostic;
+use common::Solution;
+use std::collections::HashMap;
+use std::error::Error;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 2;
+
+    type Input<'a> = Vec<&'a str>;
+    type One = usize;
+    type Two = String;
+
+    /// Box IDs of lowercase letters, all of the same length
+    fn parse(input: &str) -> Result<Vec<&str>, Box<dyn Error>> {
+        let len = input.lines().next().map_or(0, str::len);
+        input
+            .lines()
+            .map(|id| {
+                if let Some(i) = id.find(|c: char| !c.is_ascii_lowercase()) {
+                    return Err(Diagnostic::new(input, &id[i..], "a lowercase letter").into());
+                }
+                if id.len() != len {
+                    let at = &id[len.min(id.len())..];
+                    let expected = format!("an ID of {} letters like the first one", len);
+                    return Err(Diagnostic::new(input, at, expected).into());
+                }
+                Ok(id)
+            })
+            .collect()
+    }
+
+    /// Checksum
+    fn part_one(box_ids: &Vec<&str>) -> Result<usize, Box<dyn Error>> {
+        let checksum_maps: Vec<_> = box_ids
+            .iter()
+            .map(|line| {
+                line.chars().fold(HashMap::new(), |mut map, c| {
+                    *map.entry(c).or_insert(0usize) += 1;
+                    map
+                })
+            })
+            .collect();
+
+        let (two, three) = checksum_maps.iter().fold((0usize, 0), |(two, three), map| {
+            match map.values().fold((0usize, 0), |(two, three), n| match n {
+                2 => (two + 1, three),
+                3 => (two, three + 1),
+                _ => (two, three),
+            }) {
+                (0, 0) => (two, three),
+                (0, _) => (two, three + 1),
+                (_, 0) => (two + 1, three),
+                _ => (two + 1, three + 1),
+            }
+        });
+        Ok(two * three)
+    }
+
+    /// Letters in common of the two correct box ids
+    fn part_two(box_ids: &Vec<&str>) -> Result<String, Box<dyn Error>> {
+        let possible_box_ids: Vec<_> = box_ids
+            .iter()
+            .magic()
+            .filter_map(|(iter, id)| {
+                let possible_box_ids: Vec<_> = iter
+                    .filter(|other_id| {
+                        id.chars()
+                            .zip(other_id.chars())
+                            .filter(|(a, b)| a != b)
+                            .count()
+                            == 1
+                    })
+                    .collect();
+
+                if !possible_box_ids.is_empty() {
+                    Some((id, possible_box_ids))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        let mut correct_box_ids = possible_box_ids.iter().flat_map(|(id, possible_box_ids)| {
+            possible_box_ids.iter().map(move |other_id| {
+                id.chars()
+                    .zip(other_id.chars())
+                    .filter_map(|(a, b)| if a == b { Some(a) } else { None })
+                    .collect::<String>()
+            })
+        });
+        let correct_box_id = correct_box_ids.next().ok_or("No correct box")?;
+        if correct_box_ids.next().is_some() {
+            return Err("More than one correct box".into());
+        }
+        Ok(correct_box_id)
+    }
+}
+
+pub struct Magic<I> {
+    iter: I,
+}
+
+impl<I> Magic<I> {
+    pub fn new(iter: I) -> Self {
+        Self { iter }
+    }
+}
+
+impl<I> Iterator for Magic<I>
+where
+    I: Iterator + Clone,
+{
+    type Item = (I, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|item| (self.iter.clone(), item))
+    }
+}
+
+trait IterPlus: Iterator {
+    fn magic(self) -> Magic<Self>
+    where
+        Self: Sized,
+    {
+        Magic::new(self)
+    }
+}
+
+impl<T: ?Sized> IterPlus for T where T: Iterator {}
@@ -0,0 +1,128 @@
+#[macro_use]
+extern crate nom;
+
+use nom::types::CompleteStr;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+pub struct Claim {
+    pub pos_x: usize,
+    pub pos_y: usize,
+    pub seg_x: usize,
+    pub seg_y: usize,
+}
+
+named!(usize_dec<CompleteStr, usize>,
+  map_res!(take_while!(|c: char| c.is_ascii_digit()), |CompleteStr(s)| FromStr::from_str(s))
+);
+
+named!(claim<CompleteStr, (usize, Claim)>,
+  do_parse!(
+    tag!("#") >>
+    id: usize_dec >>
+    ws!(tag!("@")) >>
+    pos_x: usize_dec >>
+    tag!(",") >>
+    pos_y: usize_dec >>
+    ws!(tag!(":")) >>
+    seg_x: usize_dec >>
+    tag!("x") >>
+    seg_y: usize_dec >>
+    (id, Claim { pos_x, pos_y, seg_x, seg_y })
+  )
+);
+
+use common::diagnostic::Diagnostic;
+use common::grid::Grid;
+use common::image::Rgb;
+use common::Solution;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+use std::cmp;
+
+fn parse_claims(input: &str) -> Result<HashMap<usize, Claim>, Box<dyn Error>> {
+    let mut claims = HashMap::new();
+    for line in input.lines() {
+        let (id, claim) = claim(CompleteStr(line))
+            .map_err(|e| Diagnostic::nom4(input, e))?
+            .1;
+        if claims.insert(id, claim).is_some() {
+            let expected = format!("a new claim id, #{} is already claimed", id);
+            return Err(Diagnostic::new(input, line, expected).into());
+        }
+    }
+    Ok(claims)
+}
+
+type Fabric = Grid<Vec<usize>>;
+
+fn create_fabrics(claims: &HashMap<usize, Claim>) -> Fabric {
+    let (x, y) = claims.values().fold((0, 0), |(x, y), claim| {
+        (
+            cmp::max(x, claim.pos_x + claim.seg_x),
+            cmp::max(y, claim.pos_y + claim.seg_y),
+        )
+    });
+    let mut fabric = Grid::new(x, y, Vec::new());
+    for (id, claim) in claims {
+        for y in claim.pos_y..claim.pos_y + claim.seg_y {
+            for square in &mut fabric.row_mut(y)[claim.pos_x..claim.pos_x + claim.seg_x] {
+                square.push(*id);
+            }
+        }
+    }
+    fabric
+}
+
+/// Black without claim, gray with one, red when they overlap.
+fn color(square: &[usize]) -> Rgb {
+    match square.len() {
+        0 => Rgb::BLACK,
+        1 => Rgb::GRAY,
+        _ => Rgb::RED,
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 3;
+
+    type Input<'a> = HashMap<usize, Claim>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<HashMap<usize, Claim>, Box<dyn Error>> {
+        parse_claims(input)
+    }
+
+    /// Number of Square that overlap
+    fn part_one(claims: &HashMap<usize, Claim>) -> Result<usize, Box<dyn Error>> {
+        let fabric = create_fabrics(claims);
+        Ok(fabric.iter().filter(|square| square.len() > 1).count())
+    }
+
+    /// Id of the only claim that doesn't overlap
+    fn part_two(claims: &HashMap<usize, Claim>) -> Result<usize, Box<dyn Error>> {
+        let fabric = create_fabrics(claims);
+        let claims_id: HashSet<_> = claims.keys().collect();
+        let claims_overlaps: HashSet<_> = fabric
+            .iter()
+            .filter(|square| square.len() > 1)
+            .flatten()
+            .collect();
+        let mut not_overlaps = claims_id.difference(&claims_overlaps);
+        let not_overlap = not_overlaps.next().ok_or("Every claim overlaps")?;
+        if not_overlaps.next().is_some() {
+            return Err("More than one claim doesn't overlap".into());
+        }
+        Ok(**not_overlap)
+    }
+
+    /// The claims on the fabric and their overlaps
+    fn image(claims: &HashMap<usize, Claim>) -> Result<Option<Grid<Rgb>>, Box<dyn Error>> {
+        Ok(Some(create_fabrics(claims).map(|square| color(square))))
+    }
+}
@@ -0,0 +1,206 @@
+#[macro_use]
+extern crate nom;
+extern crate chrono;
+
+use chrono::naive::{NaiveDate, NaiveDateTime};
+use chrono::{Days, Timelike};
+use common::diagnostic::Diagnostic;
+use common::random::Rng;
+use common::Solution;
+use nom::types::CompleteStr;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct Event {
+    date_time: NaiveDateTime,
+    action: Action,
+}
+
+#[derive(Debug)]
+pub enum Action {
+    Sleep,
+    Wake,
+    Shift(usize),
+}
+
+named!(usize_dec<CompleteStr, usize>,
+    map_res!(take_while!(|c: char| c.is_ascii_digit()), |CompleteStr(s)| FromStr::from_str(s))
+);
+
+named!(parse_date_time<CompleteStr, NaiveDateTime>,
+    map_res!(delimited!(char!('['), take_till!(|ch| ch == ']'), char!(']')),
+    |CompleteStr(s)| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M"))
+);
+
+named!(parse_shift<CompleteStr, Action>,
+    do_parse!(
+        ws!(tag!("Guard")) >>
+        ws!(tag!("#")) >>
+        id: usize_dec >>
+        ws!(tag!("begins")) >>
+        ws!(tag!("shift")) >>
+        (Action::Shift(id))
+    )
+);
+
+named!(parse_action<CompleteStr, Action>,
+    alt!(
+        do_parse!(ws!(tag!("falls")) >> ws!(tag!("asleep")) >> (Action::Sleep)) |
+        do_parse!(ws!(tag!("wakes")) >> ws!(tag!("up")) >> (Action::Wake)) |
+        parse_shift
+    )
+);
+
+named!(event<CompleteStr, Event>,
+  do_parse!(
+    date_time: ws!(parse_date_time) >>
+    action: ws!(parse_action) >>
+    (Event { date_time, action })
+  )
+);
+
+fn parse_events(input: &str) -> Result<Vec<Event>, Box<dyn Error>> {
+    let mut events = input
+        .lines()
+        .map(|line| {
+            Ok(event(CompleteStr(line))
+                .map_err(|e| Diagnostic::nom4(input, e))?
+                .1)
+        })
+        .collect::<Result<Vec<Event>, Box<dyn Error>>>()?;
+    events.sort_unstable_by_key(|event| event.date_time);
+    Ok(events)
+}
+
+/// Per guard, the minutes asleep in total and at each minute of the midnight hour. Sorted by
+/// id so the answers break ties the same way every run.
+pub type Stats = BTreeMap<usize, (u32, Vec<u32>)>;
+
+/// The naps of the guards, the events sorted by time like `Day4::parse` does.
+pub fn create_stats(events: &[Event]) -> Result<Stats, Box<dyn Error>> {
+    let mut id = None;
+    let mut time = None;
+    let mut stats = BTreeMap::new();
+    for event in events.iter() {
+        match event.action {
+            Action::Shift(shift) => {
+                id = Some(shift);
+                if time.is_some() {
+                    Err(format!("Shift before Wake: {:?}", event))?;
+                }
+            }
+            Action::Sleep => {
+                id.ok_or("Sleep before Shift")?;
+                if time.is_some() {
+                    Err(format!("Sleep twice: {:?}", event))?;
+                }
+                time = Some(event.date_time.time())
+            }
+            Action::Wake => {
+                let time = time.take().ok_or("Wake before Sleep")?;
+                let id = id.ok_or("Wake before Shift")?;
+                let start = time.minute();
+                let end = event.date_time.time().minute();
+                let (total, counts) = stats.entry(id).or_insert((0, vec![0; 60]));
+                *total += end - start;
+                for step in &mut counts[start as usize..end as usize] {
+                    *step += 1;
+                }
+            }
+        }
+    }
+    Ok(stats)
+}
+
+fn create_results(stats: &Stats) -> Result<Vec<(usize, u32)>, Box<dyn Error>> {
+    let mut iter = stats.iter();
+    let (id, (_, counts)) = iter.next().ok_or("Stats is empty")?;
+    let mut results: Vec<_> = counts.iter().map(|count| (*id, *count)).collect();
+    for (id, (_, counts)) in iter {
+        for ((prev_id, prev_count), count) in results.iter_mut().zip(counts.iter()) {
+            if *prev_count < *count {
+                *prev_id = *id;
+                *prev_count = *count;
+            }
+        }
+    }
+    Ok(results)
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 4;
+
+    type Input<'a> = Vec<Event>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Vec<Event>, Box<dyn Error>> {
+        parse_events(input)
+    }
+
+    /// Strategie 1
+    fn part_one(events: &Vec<Event>) -> Result<usize, Box<dyn Error>> {
+        let stats = create_stats(events)?;
+        let answer = stats
+            .iter()
+            .max_by_key(|(_, (total, _))| total)
+            .map(|(id, (_, counts))| {
+                counts
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|(_, count)| *count)
+                    .map(|(index, _)| index * id)
+                    .ok_or("There is no count")
+            })
+            .ok_or("There is no guard")??;
+        Ok(answer)
+    }
+
+    /// Strategie 2
+    fn part_two(events: &Vec<Event>) -> Result<usize, Box<dyn Error>> {
+        let stats = create_stats(events)?;
+        let results = create_results(&stats)?;
+        Ok(results
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, (_, count))| count)
+            .map(|(index, (id, _))| id * index)
+            .ok_or("There is no result")?)
+    }
+
+    /// `size` shifts of a few guards, the lines out of order like the real logs
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let guards: Vec<_> = (0..size / 20 + 2).map(|_| 1 + rng.below(3499)).collect();
+        let first = NaiveDate::from_ymd_opt(1518, 3, 1)?;
+        let mut lines = Vec::new();
+        for shift in 0..size {
+            let day = first.checked_add_days(Days::new(shift as u64))?;
+            let guard = rng.choose(&guards)?;
+            // the shift begins a bit before or after midnight, before the first nap
+            let begin = if rng.chance(0.5) {
+                format!("{} 23:{:02}", day.pred_opt()?, 45 + rng.below(15))
+            } else {
+                format!("{} 00:{:02}", day, rng.below(4))
+            };
+            lines.push(format!("[{}] Guard #{} begins shift", begin, guard));
+
+            // at least a nap the first shift, a guard must sleep
+            let mut naps = usize::from(shift == 0) + rng.below(4);
+            let mut minute = 5 + rng.below(15);
+            while naps > 0 && minute < 58 {
+                let wake = minute + 1 + rng.below((59 - minute).min(25));
+                lines.push(format!("[{} 00:{:02}] falls asleep", day, minute));
+                lines.push(format!("[{} 00:{:02}] wakes up", day, wake));
+                minute = wake + 1 + rng.below(10);
+                naps -= 1;
+            }
+        }
+        rng.shuffle(&mut lines);
+        Some(lines.iter().map(|line| format!("{}\n", line)).collect())
+    }
+}
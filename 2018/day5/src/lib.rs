@@ -0,0 +1,200 @@
+use common::animation::{Animation, Paint};
+use common::diagnostic::Diagnostic;
+use common::image::Rgb;
+use common::random::Rng;
+use common::Solution;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+/// The polymer once every pair of units of the same type and opposite polarities annihilated.
+pub fn strip_polymer(polymer: impl Iterator<Item = char>) -> Result<String, Box<dyn Error>> {
+    react(polymer, |_, _| {})
+}
+
+/// `strip_polymer`, `annihilated` sees the polymer reacted so far and the unit that
+/// annihilates its last one.
+fn react<F>(
+    polymer: impl Iterator<Item = char>,
+    mut annihilated: F,
+) -> Result<String, Box<dyn Error>>
+where
+    F: FnMut(&str, char),
+{
+    let mut react = String::with_capacity(polymer.size_hint().1.unwrap_or(0));
+    for unit in polymer {
+        if !unit.is_ascii_alphabetic() {
+            return Err("Unit must be a character in lowercase or uppercase".into());
+        }
+        if react
+            .chars()
+            .next_back()
+            .map(
+                |last| match (last.is_ascii_lowercase(), unit.is_ascii_lowercase()) {
+                    (false, true) => last.to_ascii_lowercase() == unit,
+                    (true, false) => last == unit.to_ascii_lowercase(),
+                    _ => false,
+                },
+            )
+            .unwrap_or(false)
+        {
+            annihilated(&react, unit);
+            react.pop();
+        } else {
+            react.push(unit);
+        }
+    }
+    Ok(react)
+}
+
+/// The end of the polymer with the pair of units annihilating in red.
+fn frame(react: &str, unit: char, count: usize) -> String {
+    // the units are ASCII letters
+    let (rest, last) = react.split_at(react.len() - 1);
+    let rest = &rest[rest.len().saturating_sub(70)..];
+    format!(
+        "{}{}{}\n{} pairs annihilated\n",
+        rest,
+        Paint(last, Rgb::RED),
+        Paint(unit, Rgb::RED),
+        count
+    )
+}
+
+/// The unit of the same type and the opposite polarity.
+fn opposite(unit: char) -> char {
+    if unit.is_ascii_lowercase() {
+        unit.to_ascii_uppercase()
+    } else {
+        unit.to_ascii_lowercase()
+    }
+}
+
+/// An answer per polymer of the input, in order.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Lengths(pub Vec<usize>);
+
+/// A line per polymer.
+impl fmt::Display for Lengths {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, length) in self.0.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", length)?;
+        }
+        Ok(())
+    }
+}
+
+/// The length alone for a single polymer.
+impl fmt::Debug for Lengths {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0.as_slice() {
+            [length] => write!(f, "{:?}", length),
+            lengths => write!(f, "{:?}", lengths),
+        }
+    }
+}
+
+/// Shortest length of `polymer` with one unit type removed.
+fn shortest(polymer: &str) -> Result<usize, Box<dyn Error>> {
+    let polymer_stripped = strip_polymer(polymer.chars())?;
+    let len_normal = polymer_stripped.len();
+    let units: HashSet<_> = polymer_stripped
+        .chars()
+        .map(|unit| unit.to_ascii_lowercase())
+        .collect();
+    units.into_iter().try_fold(len_normal, |acc, unit| {
+        let polymer_filtered = polymer_stripped
+            .chars()
+            .filter(|c| !unit.eq_ignore_ascii_case(c));
+        strip_polymer(polymer_filtered)
+            .map(|polymer_stripped| std::cmp::min(acc, polymer_stripped.len()))
+    })
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 5;
+
+    /// A polymer per line
+    type Input<'a> = Vec<&'a str>;
+    type One = Lengths;
+    type Two = Lengths;
+
+    fn parse(input: &str) -> Result<Vec<&str>, Box<dyn Error>> {
+        input
+            .lines()
+            .map(
+                |polymer| match polymer.find(|unit: char| !unit.is_ascii_alphabetic()) {
+                    Some(i) => Err(Diagnostic::new(
+                        input,
+                        &polymer[i..],
+                        "a unit, a letter in lowercase or uppercase",
+                    )
+                    .into()),
+                    None => Ok(polymer),
+                },
+            )
+            .collect()
+    }
+
+    /// Length of each polymer once fully reacted
+    fn part_one(polymers: &Vec<&str>) -> Result<Lengths, Box<dyn Error>> {
+        polymers
+            .iter()
+            .map(|polymer| Ok(strip_polymer(polymer.chars())?.len()))
+            .collect::<Result<_, _>>()
+            .map(Lengths)
+    }
+
+    /// Shortest length of each polymer with one unit type removed
+    fn part_two(polymers: &Vec<&str>) -> Result<Lengths, Box<dyn Error>> {
+        polymers
+            .iter()
+            .map(|polymer| shortest(polymer))
+            .collect::<Result<_, _>>()
+            .map(Lengths)
+    }
+
+    /// The units annihilating pair by pair, one polymer after the other
+    fn animate(polymers: &Vec<&str>, animation: &mut Animation) -> Result<bool, Box<dyn Error>> {
+        let mut count = 0;
+        for polymer in polymers {
+            react(polymer.chars(), |react, unit| {
+                count += 1;
+                animation.frame(frame(react, unit, count));
+            })?;
+        }
+        Ok(true)
+    }
+
+    /// `size` units, nested pairs annihilate like in the real polymers
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut polymer = String::with_capacity(size + 1);
+        // the units not annihilated yet by the units generated
+        let mut react = Vec::new();
+        for _ in 0..size {
+            let unit = match react.pop() {
+                Some(last) if rng.chance(0.45) => opposite(last),
+                last => {
+                    react.extend(last);
+                    let unit = char::from(b'a' + rng.below(26) as u8);
+                    let unit = if rng.chance(0.5) {
+                        unit.to_ascii_uppercase()
+                    } else {
+                        unit
+                    };
+                    react.push(unit);
+                    unit
+                }
+            };
+            polymer.push(unit);
+        }
+        polymer.push('\n');
+        Some(polymer)
+    }
+}
@@ -0,0 +1,223 @@
+#[macro_use]
+extern crate nom;
+
+use common::diagnostic::Diagnostic;
+use common::geometry::{Bounds, Vec2};
+use common::grid::Grid;
+use common::image::{Palette, Rgb};
+use common::random::Rng;
+use common::Solution;
+use nom::types::CompleteStr;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::str::FromStr;
+
+fn parse_coords(input: &str) -> Result<Vec<Vec2>, Box<dyn Error>> {
+    let coords = input
+        .lines()
+        .map(|line| {
+            Ok(coord(CompleteStr(line))
+                .map_err(|e| Diagnostic::nom4(input, e))?
+                .1)
+        })
+        .collect::<Result<Vec<Vec2>, Box<dyn Error>>>()?;
+    Ok(coords)
+}
+
+named!(i64_dec<CompleteStr, i64>,
+    map_res!(take_while!(|c: char| c.is_ascii_digit()), |CompleteStr(s)| FromStr::from_str(s))
+);
+
+named!(coord<CompleteStr, Vec2>,
+  do_parse!(
+    x: ws!(i64_dec) >>
+    ws!(char!(',')) >>
+    y: ws!(i64_dec) >>
+    (Vec2::new(x, y))
+  )
+);
+
+#[derive(Debug)]
+enum State {
+    Visited(usize),
+    NotVisited,
+}
+
+use std::collections::{HashSet, VecDeque};
+
+type Plan<T> = Grid<T>;
+
+fn create_plans(coords: &[Vec2]) -> Result<Vec<Plan<State>>, Box<dyn Error>> {
+    let bounds = Bounds::new(coords.iter().copied()).ok_or("At least one coordonate")?;
+    let width = usize::try_from(bounds.width())?;
+    let height = usize::try_from(bounds.height())?;
+
+    coords
+        .iter()
+        .map(|coord| {
+            let start = bounds.to_pos(*coord).ok_or("Coordonate out of bounds")?;
+            let mut plan = Grid::from_fn(width, height, |_| State::NotVisited);
+            let mut queue = VecDeque::new();
+
+            queue.push_back((start, 0));
+            while let Some((pos, i)) = queue.pop_front() {
+                plan[pos] = match plan[pos] {
+                    State::NotVisited => State::Visited(i),
+                    State::Visited(_) => {
+                        continue;
+                    }
+                };
+
+                for next in plan.neighbors4(pos) {
+                    queue.push_back((next, i + 1));
+                }
+            }
+            Ok(plan)
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+enum Closest {
+    Equal(usize),
+    Id(usize, usize),
+}
+
+/// A color per coordinate, darker on the coordinate, white where several are the closest.
+impl Palette for Closest {
+    fn color(&self) -> Rgb {
+        match self {
+            Closest::Id(_, 0) => Rgb::BLACK,
+            Closest::Id(id, _) => Rgb::distinct(*id),
+            Closest::Equal(_) => Rgb::WHITE,
+        }
+    }
+}
+
+fn create_closest(plans: &[Plan<State>]) -> Result<Plan<Closest>, Box<dyn Error>> {
+    let mut iter = plans.iter().enumerate();
+    let mut closest = iter
+        .next()
+        .map(|(id, plan)| {
+            plan.map(|state| match state {
+                State::NotVisited => panic!("Bug"),
+                State::Visited(n) => Closest::Id(id, *n),
+            })
+        })
+        .ok_or("At least one coordonate")?;
+    for (id, plan) in iter {
+        for (closest, state) in closest.iter_mut().zip(plan.iter()) {
+            let i = match state {
+                State::NotVisited => panic!("Bug"),
+                State::Visited(n) => *n,
+            };
+            *closest = match closest {
+                Closest::Id(_, n) | Closest::Equal(n) => {
+                    if i < *n {
+                        Closest::Id(id, i)
+                    } else if i == *n {
+                        Closest::Equal(i)
+                    } else {
+                        continue;
+                    }
+                }
+            };
+        }
+    }
+    Ok(closest)
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 6;
+
+    type Input<'a> = Vec<Vec2>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Vec<Vec2>, Box<dyn Error>> {
+        parse_coords(input)
+    }
+
+    /// Size of the largest area that isn't infinite
+    fn part_one(coords: &Vec<Vec2>) -> Result<usize, Box<dyn Error>> {
+        let plans = create_plans(coords)?;
+        let closest = create_closest(&plans)?;
+
+        let infinite_ids: HashSet<_> = closest
+            .border()
+            .filter_map(|pos| match closest[pos] {
+                Closest::Id(id, _) => Some(id),
+                Closest::Equal(_) => None,
+            })
+            .collect();
+
+        let mut counts = vec![0; coords.len()];
+        for state in closest.iter() {
+            if let Closest::Id(id, _) = state {
+                counts[*id] += 1;
+            }
+        }
+
+        let mut iter = coords.iter().enumerate().filter_map(|(id, _)| {
+            if !infinite_ids.contains(&id) {
+                Some(id)
+            } else {
+                None
+            }
+        });
+
+        let init = iter.next().ok_or("No answer possible")?;
+        let answer = iter.fold(
+            init,
+            |acc, id| if counts[acc] < counts[id] { id } else { acc },
+        );
+        Ok(counts[answer])
+    }
+
+    /// Size of the region close to every coordinate
+    fn part_two(coords: &Vec<Vec2>) -> Result<usize, Box<dyn Error>> {
+        let plans = create_plans(coords)?;
+
+        let mut iter = plans.iter();
+        let mut totals = iter
+            .next()
+            .map(|plan| {
+                plan.map(|state| match state {
+                    State::NotVisited => panic!("Bug"),
+                    State::Visited(n) => *n,
+                })
+            })
+            .ok_or("At least one coordonate")?;
+        for plan in iter {
+            for (total, state) in totals.iter_mut().zip(plan.iter()) {
+                let i = match state {
+                    State::NotVisited => panic!("Bug"),
+                    State::Visited(n) => *n,
+                };
+                *total += i;
+            }
+        }
+
+        let answer = totals.iter().filter(|total| **total < 10_000).count();
+        Ok(answer)
+    }
+
+    /// The area closest to each coordinate
+    fn image(coords: &Vec<Vec2>) -> Result<Option<Grid<Rgb>>, Box<dyn Error>> {
+        let closest = create_closest(&create_plans(coords)?)?;
+        Ok(Some(closest.map(Palette::color)))
+    }
+
+    /// `size` coordinates, on a square growing with them as spread as the real ones
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let side = (50. * (size as f64).sqrt()).ceil() as usize + 1;
+        Some(
+            (0..size)
+                .map(|_| format!("{}, {}\n", rng.below(side), rng.below(side)))
+                .collect(),
+        )
+    }
+}
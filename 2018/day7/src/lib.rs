@@ -0,0 +1,308 @@
+#[macro_use]
+extern crate nom;
+
+use common::animation::{Animation, Paint};
+use common::diagnostic::Diagnostic;
+use common::image::Rgb;
+use common::random::Rng;
+use common::Solution;
+use nom::types::CompleteStr;
+use std::error::Error;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Step {
+    instruction: char,
+    requiere: char,
+}
+
+// Step B must be finished before step X can begin.
+
+named!(step<CompleteStr, Step>,
+  do_parse!(
+    ws!(tag!("Step")) >>
+    requiere: ws!(none_of!("")) >>
+    ws!(tag!("must")) >>
+    ws!(tag!("be")) >>
+    ws!(tag!("finished")) >>
+    ws!(tag!("before")) >>
+    ws!(tag!("step")) >>
+    instruction: ws!(none_of!("")) >>
+    ws!(tag!("can")) >>
+    ws!(tag!("begin")) >>
+    ws!(char!('.'))>>
+
+    (Step { instruction, requiere })
+  )
+);
+
+fn parse_inputs(input: &str) -> Result<Vec<Step>, Box<dyn Error>> {
+    let inputs = input
+        .lines()
+        .map(|line| {
+            let step = step(CompleteStr(line))
+                .map_err(|e| Diagnostic::nom4(input, e))?
+                .1;
+            // the names are the second and the eighth words
+            for name in line.split_whitespace().skip(1).step_by(6) {
+                if !(name.len() == 1 && name.chars().all(|c| c.is_ascii_uppercase())) {
+                    let expected = "a step name, an uppercase letter";
+                    return Err(Diagnostic::new(input, name, expected).into());
+                }
+            }
+            Ok(step)
+        })
+        .collect::<Result<Vec<Step>, Box<dyn Error>>>()?;
+    Ok(inputs)
+}
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[derive(Eq)]
+struct Instruction {
+    instruction: char,
+    requires: Vec<Rc<RefCell<Instruction>>>,
+    done: bool,
+    allows: Vec<Rc<RefCell<Instruction>>>,
+}
+
+impl Ord for Instruction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.instruction.cmp(&other.instruction)
+    }
+}
+
+impl PartialOrd for Instruction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Instruction {
+    fn eq(&self, other: &Self) -> bool {
+        self.instruction == other.instruction
+    }
+}
+
+fn create_instructions(inputs: &[Step]) -> HashMap<char, Rc<RefCell<Instruction>>> {
+    let mut instructions = HashMap::new();
+    for input in inputs {
+        let require = instructions
+            .entry(input.requiere)
+            .or_insert_with(|| {
+                Rc::new(RefCell::new(Instruction {
+                    requires: Vec::new(),
+                    done: false,
+                    allows: Vec::new(),
+                    instruction: input.requiere,
+                }))
+            })
+            .clone();
+        let allow = instructions.entry(input.instruction).or_insert_with(|| {
+            Rc::new(RefCell::new(Instruction {
+                requires: Vec::new(),
+                done: false,
+                allows: Vec::new(),
+                instruction: input.instruction,
+            }))
+        });
+        require.borrow_mut().allows.push(allow.clone());
+        allow.borrow_mut().requires.push(require);
+    }
+    instructions
+}
+
+fn answer_one(instructions: &HashMap<char, Rc<RefCell<Instruction>>>) -> String {
+    let mut work = create_work(instructions);
+    let mut answer = String::new();
+    while let Some(instruction) = work.pop() {
+        answer.push(instruction.borrow().instruction);
+        instruction.borrow_mut().done = true;
+        for allow in instruction.borrow().allows.iter() {
+            if allow
+                .borrow()
+                .requires
+                .iter()
+                .all(|require| require.borrow().done)
+            {
+                work.push(allow.clone());
+            }
+        }
+    }
+    answer
+}
+
+use binary_heap_plus::{BinaryHeap, MinComparator};
+
+fn create_work(
+    instructions: &HashMap<char, Rc<RefCell<Instruction>>>,
+) -> BinaryHeap<Rc<RefCell<Instruction>>, MinComparator> {
+    let mut work = BinaryHeap::new_min();
+
+    for instruction in instructions
+        .values()
+        .filter(|instruction| instruction.borrow().requires.is_empty())
+        .cloned()
+    {
+        work.push(instruction);
+    }
+    work
+}
+
+#[derive(Eq)]
+struct Worker {
+    instruction: Rc<RefCell<Instruction>>,
+    time: i32,
+}
+
+impl Ord for Worker {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.time.cmp(&other.time)
+    }
+}
+
+impl PartialOrd for Worker {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Worker {
+    fn eq(&self, other: &Self) -> bool {
+        self.time == other.time
+    }
+}
+
+fn answer_two(
+    instructions: &HashMap<char, Rc<RefCell<Instruction>>>,
+) -> Result<i32, Box<dyn Error>> {
+    schedule(instructions, |_, _, _| {})
+}
+
+type Workers = BinaryHeap<Worker, MinComparator>;
+
+/// `answer_two`, `finished` sees the second a step is finished, the step and the workers then.
+fn schedule<F>(
+    instructions: &HashMap<char, Rc<RefCell<Instruction>>>,
+    mut finished: F,
+) -> Result<i32, Box<dyn Error>>
+where
+    F: FnMut(i32, char, &Workers),
+{
+    let mut work = create_work(instructions);
+    let max_worker = 5;
+    let times: HashMap<_, _> = "ABCDEFGHIJKLMNOPQRSTUVWXYZ".chars().zip(61..).collect();
+
+    let mut workers = BinaryHeap::new_min();
+    while workers.len() < max_worker {
+        if let Some(instruction) = work.pop() {
+            let time = *times
+                .get(&instruction.borrow().instruction)
+                .ok_or("A instruction doesn't have time")?;
+            workers.push(Worker { instruction, time });
+        } else {
+            break;
+        }
+    }
+    let mut result = 0;
+    while let Some(worker) = workers.pop() {
+        let instruction = worker.instruction;
+        result = worker.time;
+        instruction.borrow_mut().done = true;
+        for allow in instruction.borrow().allows.iter() {
+            if allow
+                .borrow()
+                .requires
+                .iter()
+                .all(|require| require.borrow().done)
+            {
+                work.push(allow.clone());
+            }
+        }
+        while workers.len() < max_worker {
+            if let Some(instruction) = work.pop() {
+                let time = *times
+                    .get(&instruction.borrow().instruction)
+                    .ok_or("A instruction doesn't have time")?;
+                workers.push(Worker {
+                    instruction,
+                    time: time + worker.time,
+                });
+            } else {
+                break;
+            }
+        }
+        finished(result, instruction.borrow().instruction, &workers);
+    }
+    Ok(result)
+}
+
+/// The steps done and the steps of the workers until they are finished.
+fn frame(second: i32, done: &str, workers: &Workers) -> String {
+    let mut workers: Vec<_> = workers.iter().collect();
+    workers.sort_by_key(|worker| worker.instruction.borrow().instruction);
+    let mut frame = format!("second {}, done {}\n", second, Paint(done, Rgb::GRAY));
+    for worker in workers {
+        frame.push_str(&format!(
+            "{} until {}\n",
+            Paint(worker.instruction.borrow().instruction, Rgb::RED),
+            worker.time
+        ));
+    }
+    frame
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 7;
+
+    type Input<'a> = Vec<Step>;
+    type One = String;
+    type Two = i32;
+
+    fn parse(input: &str) -> Result<Vec<Step>, Box<dyn Error>> {
+        parse_inputs(input)
+    }
+
+    fn part_one(inputs: &Vec<Step>) -> Result<String, Box<dyn Error>> {
+        Ok(answer_one(&create_instructions(inputs)))
+    }
+
+    fn part_two(inputs: &Vec<Step>) -> Result<i32, Box<dyn Error>> {
+        answer_two(&create_instructions(inputs))
+    }
+
+    /// The workers taking the steps second by second
+    fn animate(inputs: &Vec<Step>, animation: &mut Animation) -> Result<bool, Box<dyn Error>> {
+        let mut done = String::new();
+        schedule(&create_instructions(inputs), |second, step, workers| {
+            done.push(step);
+            animation.frame(frame(second, &done, workers));
+        })?;
+        Ok(true)
+    }
+
+    /// `size` steps, at most the 26 letters, each after one to three of the steps before
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut steps: Vec<_> = ('A'..='Z').collect();
+        rng.shuffle(&mut steps);
+        steps.truncate(size);
+        let mut lines = Vec::new();
+        for (i, step) in steps.iter().enumerate().skip(1) {
+            let mut requires = steps[..i].to_vec();
+            rng.shuffle(&mut requires);
+            for require in requires.iter().take(1 + rng.below(3)) {
+                lines.push(format!(
+                    "Step {} must be finished before step {} can begin.\n",
+                    require, step
+                ));
+            }
+        }
+        rng.shuffle(&mut lines);
+        Some(lines.concat())
+    }
+}
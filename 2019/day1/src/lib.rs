@@ -0,0 +1,66 @@
+use common::diagnostic::Diagnostic;
+use common::Solution;
+use std::error::Error;
+
+fn answer_one(inputs: &[u32]) -> u32 {
+    inputs
+        .iter()
+        .copied()
+        .filter_map(|i| {
+            let r = i / 3;
+            if r > 2 {
+                Some(r - 2)
+            } else {
+                None
+            }
+        })
+        .sum()
+}
+
+fn answer_two(inputs: &[u32]) -> u32 {
+    inputs
+        .iter()
+        .copied()
+        .map(|i| {
+            let calc = |i: &u32| {
+                let r = i / 3;
+                if r > 2 {
+                    Some(r - 2)
+                } else {
+                    None
+                }
+            };
+            std::iter::successors(calc(&i), calc).sum::<u32>()
+        })
+        .sum::<u32>()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 1;
+
+    type Input<'a> = Vec<u32>;
+    type One = u32;
+    type Two = u32;
+
+    fn parse(input: &str) -> Result<Vec<u32>, Box<dyn Error>> {
+        input
+            .lines()
+            .map(|line| {
+                Ok(line
+                    .parse()
+                    .map_err(|e| Diagnostic::new(input, line, format!("a mass ({})", e)))?)
+            })
+            .collect()
+    }
+
+    fn part_one(inputs: &Vec<u32>) -> Result<u32, Box<dyn Error>> {
+        Ok(answer_one(inputs))
+    }
+
+    fn part_two(inputs: &Vec<u32>) -> Result<u32, Box<dyn Error>> {
+        Ok(answer_two(inputs))
+    }
+}
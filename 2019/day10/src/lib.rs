@@ -0,0 +1,214 @@
+use common::animation::{Animation, Paint};
+use common::diagnostic::Diagnostic;
+use common::geometry::Vec2;
+use common::grid::Grid;
+use common::image::Rgb;
+use common::random::Rng;
+use common::Solution;
+use std::convert::TryFrom;
+use std::error::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Object {
+    Asteriod,
+    Nothing,
+}
+
+impl Object {
+    fn is_asteriod(&self) -> bool {
+        matches!(self, Object::Asteriod)
+    }
+}
+
+fn asteroids(input: &str) -> Result<Grid<Object>, Diagnostic> {
+    Grid::parse(input, "`#` or `.`", |c| match c {
+        '#' => Some(Object::Asteriod),
+        '.' => Some(Object::Nothing),
+        _ => None,
+    })
+}
+
+/// No asteroid between `a` and `b`.
+fn calc_sight(asteroids: &Grid<Object>, a: Vec2, b: Vec2) -> bool {
+    let step = (b - a).direction();
+    (1..(b - a).gcd() as i64).all(|n| {
+        !(a + step * n)
+            .to_pos()
+            .and_then(|pos| asteroids.get(pos))
+            .is_some_and(Object::is_asteriod)
+    })
+}
+
+/// The asteroids of the grid as vectors.
+fn vectors(asteroids: &Grid<Object>) -> impl Iterator<Item = Vec2> + '_ {
+    asteroids
+        .cells()
+        .filter(|(_, object)| object.is_asteriod())
+        .filter_map(|(pos, _)| Vec2::from_pos(pos))
+}
+
+/// The asteroid that detects the most others, with their number.
+pub fn calc_one(asteroids: &Grid<Object>) -> Option<(Vec2, usize)> {
+    vectors(asteroids)
+        .map(|a| {
+            let count = vectors(asteroids)
+                .filter(|b| a != *b && calc_sight(asteroids, a, *b))
+                .count();
+            (a, count)
+        })
+        .max_by_key(|(_, count)| *count)
+}
+
+/// An asteroid seen from the station, `m` from the station to `i`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Asteriod {
+    m: Vec2,
+    i: Vec2,
+}
+
+use std::cmp::Ordering;
+
+/// Clockwise from up, the closest first.
+impl Ord for Asteriod {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.m
+            .cmp_clockwise(&other.m)
+            .then_with(|| self.m.manhattan().cmp(&other.m.manhattan()))
+            .then_with(|| self.i.cmp(&other.i))
+    }
+}
+
+impl PartialOrd for Asteriod {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Vaporize the asteroids in order, `vaporized` sees the grid left after each of them.
+fn vaporize<F>(asteroids: &Grid<Object>, station: Vec2, mut vaporized: F)
+where
+    F: FnMut(&Grid<Object>, Vec2),
+{
+    let mut asteroids = asteroids.clone();
+    if let Some(pos) = station.to_pos() {
+        asteroids[pos] = Object::Nothing;
+    }
+    let mut to_destroy: Vec<_> = vectors(&asteroids)
+        .map(|i| Asteriod { m: i - station, i })
+        .collect();
+    to_destroy.sort();
+
+    while !to_destroy.is_empty() {
+        let destroyed: Vec<_> = to_destroy
+            .extract_if(.., |asteroid| calc_sight(&asteroids, station, asteroid.i))
+            .collect();
+        for i in destroyed {
+            if let Some(pos) = i.i.to_pos() {
+                asteroids[pos] = Object::Nothing;
+            }
+            vaporized(&asteroids, i.i);
+        }
+    }
+}
+
+/// The asteroids in the order the laser of `station` vaporizes them.
+pub fn calc_two(asteroids: &Grid<Object>, station: Vec2) -> Vec<Vec2> {
+    let mut result = Vec::new();
+    vaporize(asteroids, station, |_, asteroid| result.push(asteroid));
+    result
+}
+
+/// The asteroids left, the station and the last one vaporized in red.
+fn frame(asteroids: &Grid<Object>, station: Vec2, vaporized: Vec2, count: usize) -> String {
+    let mut frame = String::new();
+    for (y, row) in asteroids.rows().enumerate() {
+        for (x, object) in row.iter().enumerate() {
+            let cell = Vec2::from_pos((x, y));
+            let c = if cell == Some(station) {
+                Paint('X', Rgb::RED).to_string()
+            } else if cell == Some(vaporized) {
+                Paint('*', Rgb::RED).to_string()
+            } else if object.is_asteriod() {
+                '#'.to_string()
+            } else {
+                '.'.to_string()
+            };
+            frame.push_str(&c);
+        }
+        frame.push('\n');
+    }
+    frame.push_str(&format!("vaporized {}: {}\n", count, vaporized));
+    frame
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 10;
+
+    type Input<'a> = Grid<Object>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Grid<Object>, Box<dyn Error>> {
+        Ok(asteroids(input)?)
+    }
+
+    /// Asteroids detected from the best location
+    fn part_one(asteroids: &Grid<Object>) -> Result<usize, Box<dyn Error>> {
+        Ok(calc_one(asteroids).ok_or("no asteroid")?.1)
+    }
+
+    /// The 200th asteroid vaporized, as `x * 100 + y`
+    fn part_two(asteroids: &Grid<Object>) -> Result<usize, Box<dyn Error>> {
+        let (station, _) = calc_one(asteroids).ok_or("no asteroid")?;
+        let two = *calc_two(asteroids, station)
+            .get(199)
+            .ok_or("less than 200 asteroids")?;
+        Ok(usize::try_from(two.x * 100 + two.y)?)
+    }
+
+    /// The asteroids in the order they are vaporized, from yellow to blue, the station in red
+    fn image(asteroids: &Grid<Object>) -> Result<Option<Grid<Rgb>>, Box<dyn Error>> {
+        let (station, _) = calc_one(asteroids).ok_or("no asteroid")?;
+        let order = calc_two(asteroids, station);
+        let mut image = asteroids.map(|_| Rgb::BLACK);
+        for (i, asteroid) in order.iter().enumerate() {
+            if let Some(pos) = asteroid.to_pos() {
+                let t = i as f64 / order.len().max(2).saturating_sub(1) as f64;
+                image[pos] = Rgb::gradient(Rgb(250, 220, 40), Rgb(40, 60, 220), t);
+            }
+        }
+        if let Some(pos) = station.to_pos() {
+            image[pos] = Rgb::RED;
+        }
+        Ok(Some(image))
+    }
+
+    /// The asteroids vaporized one by one
+    fn animate(
+        asteroids: &Grid<Object>,
+        animation: &mut Animation,
+    ) -> Result<bool, Box<dyn Error>> {
+        let (station, _) = calc_one(asteroids).ok_or("no asteroid")?;
+        let mut count = 0;
+        vaporize(asteroids, station, |left, vaporized| {
+            count += 1;
+            animation.frame(frame(left, station, vaporized, count));
+        });
+        Ok(true)
+    }
+
+    /// `size` asteroids on a square map a third full, at least 201 so part two has 200 to
+    /// vaporize besides the station
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let size = size.max(201);
+        let side = ((3 * size) as f64).sqrt().ceil() as usize;
+        let mut cells: Vec<_> = (0..side * side)
+            .map(|i| if i < size { '#' } else { '.' })
+            .collect();
+        rng.shuffle(&mut cells);
+        Some(Grid::from_vec(side, cells)?.to_string())
+    }
+}
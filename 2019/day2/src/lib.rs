@@ -0,0 +1,75 @@
+use common::diagnostic::Diagnostic;
+use common::Solution;
+use std::error::Error;
+
+fn answer_one(inputs: &[usize]) -> usize {
+    let mut ret = inputs.to_vec();
+
+    ret[1] = 12;
+    ret[2] = 2;
+    for i in (0..ret.len()).step_by(4) {
+        let n = match ret[i] {
+            1 => ret[ret[i + 1]] + ret[ret[i + 2]],
+            2 => ret[ret[i + 1]] * ret[ret[i + 2]],
+            99 => break,
+            _ => panic!("noooooooooooooo"),
+        };
+        let j = ret[i + 3];
+        ret[j] = n;
+    }
+    ret[0]
+}
+
+fn answer_two(inputs: &[usize]) -> Option<usize> {
+    for (i, j) in (0..100).flat_map(|i| (0..100).map(move |j| (i, j))) {
+        let mut ret = inputs.to_vec();
+
+        ret[1] = i;
+        ret[2] = j;
+        for i in (0..ret.len()).step_by(4) {
+            let n = match ret[i] {
+                1 => ret[ret[i + 1]] + ret[ret[i + 2]],
+                2 => ret[ret[i + 1]] * ret[ret[i + 2]],
+                99 => break,
+                _ => panic!("noooooooooooooo"),
+            };
+            let j = ret[i + 3];
+            ret[j] = n;
+        }
+        if ret[0] == 19690720 {
+            return Some(100 * i + j);
+        }
+    }
+    None
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 2;
+
+    type Input<'a> = Vec<usize>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>, Box<dyn Error>> {
+        input
+            .trim()
+            .split(',')
+            .map(|token| {
+                Ok(token.parse().map_err(|e| {
+                    Diagnostic::new(input, token, format!("a positive integer ({})", e))
+                })?)
+            })
+            .collect()
+    }
+
+    fn part_one(inputs: &Vec<usize>) -> Result<usize, Box<dyn Error>> {
+        Ok(answer_one(inputs))
+    }
+
+    fn part_two(inputs: &Vec<usize>) -> Result<usize, Box<dyn Error>> {
+        Ok(answer_two(inputs).ok_or("no solution")?)
+    }
+}
@@ -0,0 +1,264 @@
+use common::diagnostic::Diagnostic;
+use common::geometry::Vec2;
+use common::random::Rng;
+use common::Solution;
+use std::error::Error;
+
+use nom::{
+    branch::alt,
+    character::complete::{char, digit1},
+    combinator::all_consuming,
+    combinator::cut,
+    combinator::map_res,
+    combinator::recognize,
+    multi::separated_list,
+    IResult,
+};
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Sens {
+    Right,
+    Left,
+    Up,
+    Down,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Dir {
+    sens: Sens,
+    value: u32,
+}
+
+use std::str::FromStr;
+
+fn index(input: &str) -> IResult<&str, u32> {
+    map_res(recognize(digit1), u32::from_str)(input)
+}
+
+fn right(input: &str) -> IResult<&str, Sens> {
+    let (input, _) = char('R')(input)?;
+    Ok((input, Sens::Right {}))
+}
+
+fn left(input: &str) -> IResult<&str, Sens> {
+    let (input, _) = char('L')(input)?;
+    Ok((input, Sens::Left {}))
+}
+
+fn up(input: &str) -> IResult<&str, Sens> {
+    let (input, _) = char('U')(input)?;
+    Ok((input, Sens::Up {}))
+}
+
+fn down(input: &str) -> IResult<&str, Sens> {
+    let (input, _) = char('D')(input)?;
+    Ok((input, Sens::Down {}))
+}
+
+fn direction(input: &str) -> IResult<&str, Dir> {
+    let (input, sens) = alt((right, left, up, down))(input)?;
+    let (input, value) = index(input)?;
+    Ok((input, Dir { sens, value }))
+}
+
+fn directions(input: &str) -> IResult<&str, Vec<Dir>> {
+    all_consuming(separated_list(char(','), cut(direction)))(input)
+}
+
+/// A point of a wire, `step` moves from the central port.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Point {
+    pub pos: Vec2,
+    pub step: u64,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum AxeSegment {
+    X {
+        x: i64,
+        ya: i64,
+        yb: i64,
+        xstep: u64,
+    },
+    Y {
+        y: i64,
+        xa: i64,
+        xb: i64,
+        ystep: u64,
+    },
+}
+
+impl Point {
+    fn new(pos: Vec2, step: u64) -> Self {
+        Self { pos, step }
+    }
+
+    fn point(self, direction: Dir) -> Self {
+        let unit = match direction.sens {
+            Sens::Right => Vec2::RIGHT,
+            Sens::Left => Vec2::LEFT,
+            Sens::Up => Vec2::UP,
+            Sens::Down => Vec2::DOWN,
+        };
+        Self {
+            pos: self.pos + unit * i64::from(direction.value),
+            step: self.step + u64::from(direction.value),
+        }
+    }
+}
+
+fn calc_points(directions: &[Dir]) -> Vec<Point> {
+    let mut directions = directions.iter();
+    std::iter::successors(Some(Point::new(Vec2::ZERO, 0)), |point| {
+        directions.next().map(|direction| point.point(*direction))
+    })
+    .collect()
+}
+
+impl AxeSegment {
+    /*
+    fn new(a: Point, b: Point) -> Self {
+        Self { a, b }
+    }
+
+    fn pente(self) -> f32 {
+        (self.b.y - self.a.y) as f32 / (self.b.x - self.a.x) as f32
+    }*/
+}
+
+use itertools::Itertools;
+
+fn calc_segments(points: &[Point]) -> Vec<AxeSegment> {
+    points
+        .iter()
+        .copied()
+        .tuple_windows()
+        .map(|(a, b)| {
+            if a.pos.x == b.pos.x {
+                AxeSegment::X {
+                    x: a.pos.x,
+                    ya: a.pos.y,
+                    yb: b.pos.y,
+                    xstep: a.step,
+                }
+            } else if a.pos.y == b.pos.y {
+                AxeSegment::Y {
+                    y: a.pos.y,
+                    xa: a.pos.x,
+                    xb: b.pos.x,
+                    ystep: a.step,
+                }
+            } else {
+                panic!("That not possible");
+            }
+        })
+        .collect()
+}
+/*
+fn calc_pentes(points: &[Segment]) -> Vec<f32> {
+    points.iter().copied().map(Segment::pente).collect()
+}*/
+
+use std::fmt::Debug;
+
+use std::cmp::{max, min};
+
+fn calc_intersection<'a, A, B>(segments: A) -> Vec<Point>
+where
+    A: IntoIterator<Item = B> + 'a,
+    <A as IntoIterator>::IntoIter: Clone,
+    B: IntoIterator<Item = &'a AxeSegment> + Clone + Debug,
+{
+    segments
+        .into_iter()
+        .tuple_combinations()
+        .flat_map(|(a, b)| {
+            a.into_iter().flat_map(move |a| {
+                b.clone().into_iter().filter_map(move |b| match (a, b) {
+                    (AxeSegment::X { x, ya, yb, xstep }, AxeSegment::Y { y, xa, xb, ystep })
+                    | (AxeSegment::Y { y, xa, xb, ystep }, AxeSegment::X { x, ya, yb, xstep }) => {
+                        if min(xa, xb) < x && x < max(xa, xb) && min(ya, yb) < y && y < max(ya, yb)
+                        {
+                            let i = if xa < xb { x - xa } else { xa - x };
+                            let j = if ya < yb { y - ya } else { ya - y };
+                            Some(Point::new(
+                                Vec2::new(*x, *y),
+                                xstep + ystep + i.unsigned_abs() + j.unsigned_abs(),
+                            ))
+                        } else {
+                            None
+                        }
+                    }
+                    _ => None,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Where the wires cross, `step` the moves of both wires to get there.
+pub fn intersections(wires: &[Vec<Dir>]) -> Vec<Point> {
+    let points: Vec<_> = wires.iter().map(|wire| calc_points(wire)).collect();
+    let segments: Vec<_> = points.iter().map(|point| calc_segments(point)).collect();
+    calc_intersection(&segments)
+}
+
+/// `size` moves from the two of `start`, turning at every move.
+fn random_wire(rng: &mut Rng, size: usize, start: &str, mut horizontal: bool) -> String {
+    let mut wire = start.to_string();
+    for _ in 2..size {
+        let sens = match (horizontal, rng.chance(0.5)) {
+            (true, true) => 'R',
+            (true, false) => 'L',
+            (false, true) => 'U',
+            (false, false) => 'D',
+        };
+        wire.push_str(&format!(",{}{}", sens, 1 + rng.below(999)));
+        horizontal = !horizontal;
+    }
+    wire
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 3;
+
+    type Input<'a> = Vec<Vec<Dir>>;
+    type One = u64;
+    type Two = u64;
+
+    fn parse(input: &str) -> Result<Vec<Vec<Dir>>, Box<dyn Error>> {
+        input
+            .lines()
+            .map(|line| Ok(directions(line).map_err(|e| Diagnostic::nom(input, e))?.1))
+            .collect()
+    }
+
+    fn part_one(wires: &Vec<Vec<Dir>>) -> Result<u64, Box<dyn Error>> {
+        Ok(intersections(wires)
+            .iter()
+            .map(|point| point.pos.manhattan())
+            .min()
+            .ok_or("no intersection")?)
+    }
+
+    fn part_two(wires: &Vec<Vec<Dir>>) -> Result<u64, Box<dyn Error>> {
+        Ok(intersections(wires)
+            .iter()
+            .map(|point| point.step)
+            .min()
+            .ok_or("no intersection")?)
+    }
+
+    /// Two wires of `size` moves, at least 2
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        // the first moves cross each other at (10, -10), so there is always an intersection
+        Some(format!(
+            "{}\n{}\n",
+            random_wire(rng, size, "R10,U20", true),
+            random_wire(rng, size, "U10,R20", false)
+        ))
+    }
+}
@@ -0,0 +1,89 @@
+use common::diagnostic::Diagnostic;
+use common::Solution;
+use std::error::Error;
+
+use nom::{
+    character::complete::{char, digit0},
+    combinator::all_consuming,
+    combinator::map_res,
+    combinator::recognize,
+    sequence::separated_pair,
+    IResult,
+};
+
+use std::ops::Range;
+use std::str::FromStr;
+
+fn index(input: &str) -> IResult<&str, u32> {
+    map_res(recognize(digit0), u32::from_str)(input)
+}
+
+fn range(input: &str) -> IResult<&str, Range<u32>> {
+    let (input, (a, b)) = all_consuming(separated_pair(index, char('-'), index))(input)?;
+    Ok((input, a..b))
+}
+
+//use std::convert::TryFrom;
+
+/// `double` tells if a group of `i` same adjacent digits counts as the double.
+fn decompose(
+    n: u32,
+    prev: u32,
+    i: u32,
+    adjacent: bool,
+    radix: u32,
+    double: fn(u32) -> bool,
+) -> bool {
+    if n != 0 {
+        let r = n % radix;
+        if prev < r {
+            false
+        } else {
+            let n = n / radix;
+            let (i, adjacent) = if prev == r {
+                (i + 1, adjacent)
+            } else if double(i) {
+                (1, true)
+            } else {
+                (1, adjacent)
+            };
+            decompose(n, r, i, adjacent, radix, double)
+        }
+    } else {
+        double(i) || adjacent
+    }
+}
+
+fn count(range: Range<u32>, double: fn(u32) -> bool) -> usize {
+    range
+        .filter(|i| decompose(i / 10, i % 10, 1, false, 10, double))
+        //        .inspect(|i| println!("{}", i))
+        .count()
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 4;
+
+    type Input<'a> = Range<u32>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Range<u32>, Box<dyn Error>> {
+        Ok(range(input.trim())
+            .map_err(|e| Diagnostic::nom(input, e))?
+            .1)
+    }
+
+    /// At least two adjacent digits are the same
+    fn part_one(range: &Range<u32>) -> Result<usize, Box<dyn Error>> {
+        Ok(count(range.clone(), |i| i >= 2))
+    }
+
+    /// Two adjacent digits are the same and not part of a larger group
+    fn part_two(range: &Range<u32>) -> Result<usize, Box<dyn Error>> {
+        Ok(count(range.clone(), |i| i == 2))
+    }
+}
@@ -0,0 +1,107 @@
+use common::diagnostic::Diagnostic;
+use common::Solution;
+use std::error::Error;
+use std::fmt;
+
+use intcode::coverage::Coverage;
+use intcode::{instructions, State, VMError, VM};
+
+pub fn diagnostic(vm: &mut VM) -> Result<Vec<i64>, Box<dyn Error>> {
+    let mut output = Vec::new();
+    loop {
+        match vm.run()? {
+            State::Output(o) => output.push(o),
+            State::Halt => break Ok(output),
+            State::Input => break Err(Box::new(VMError::Empty)),
+            State::Continue => unreachable!(),
+        }
+    }
+}
+
+/// The diagnostic code, the last output, every test before it must output 0.
+pub fn diagnostic_code(output: &[i64]) -> Result<i64, Box<dyn Error>> {
+    let mut iter = output.iter().rev();
+    let code = iter.next().ok_or("no diagnostic code")?;
+    if !iter.all(|&i| i == 0) {
+        return Err("a diagnostic test failed".into());
+    }
+    Ok(*code)
+}
+
+/// The diagnostic code of a part with the addresses its run used.
+#[derive(Debug, Clone)]
+pub struct Covered {
+    pub code: i64,
+    pub coverage: Coverage,
+}
+
+/// Both parts run with their coverage, `--coverage` of the binary.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub one: Covered,
+    pub two: Covered,
+}
+
+/// Run the program with the input of each part, recording its coverage.
+pub fn coverage(instructions: &[i64]) -> Result<Report, Box<dyn Error>> {
+    let run = |input| -> Result<Covered, Box<dyn Error>> {
+        let mut vm = VM::new(instructions.to_vec(), Some(input));
+        vm.enable_coverage();
+        let code = diagnostic_code(&diagnostic(&mut vm)?)?;
+        let coverage = vm.coverage().cloned().ok_or("no coverage")?;
+        Ok(Covered { code, coverage })
+    };
+    Ok(Report {
+        one: run(1)?,
+        two: run(5)?,
+    })
+}
+
+impl Report {
+    /// The addresses only executed by part two, its new instructions.
+    pub fn only_two(&self) -> usize {
+        self.two
+            .coverage
+            .executed()
+            .filter(|&index| !self.one.coverage.is_executed(index))
+            .count()
+    }
+}
+
+/// The answers, both coverage maps then the count of `only_two`.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Answer One: {:?}", self.one.code)?;
+        writeln!(f, "Answer Two: {:?}", self.two.code)?;
+        writeln!(f, "Coverage One:\n{}", self.one.coverage)?;
+        writeln!(f, "Coverage Two:\n{}", self.two.coverage)?;
+        write!(f, "Addresses executed only by Two: {}", self.only_two())
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 5;
+
+    type Input<'a> = Vec<i64>;
+    type One = i64;
+    type Two = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>, Box<dyn Error>> {
+        Ok(instructions(input.trim())
+            .map_err(|e| Diagnostic::nom(input, e))?
+            .1)
+    }
+
+    fn part_one(instructions: &Vec<i64>) -> Result<i64, Box<dyn Error>> {
+        let mut vm = VM::new(instructions.clone(), Some(1));
+        diagnostic_code(&diagnostic(&mut vm)?)
+    }
+
+    fn part_two(instructions: &Vec<i64>) -> Result<i64, Box<dyn Error>> {
+        let mut vm = VM::new(instructions.clone(), Some(5));
+        diagnostic_code(&diagnostic(&mut vm)?)
+    }
+}
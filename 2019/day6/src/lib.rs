@@ -0,0 +1,111 @@
+use common::diagnostic::Diagnostic;
+use common::random::Rng;
+use common::Solution;
+use std::error::Error;
+
+use nom::{
+    character::complete::{alphanumeric1, char},
+    combinator::{all_consuming, cut},
+    multi::fold_many0,
+    sequence::{separated_pair, terminated},
+    IResult,
+};
+
+use petgraph::{
+    algo::dijkstra,
+    graphmap::UnGraphMap,
+    visit::{VisitMap, Visitable},
+};
+
+fn links(input: &str) -> IResult<&str, UnGraphMap<&str, ()>> {
+    all_consuming(fold_many0(
+        terminated(
+            separated_pair(alphanumeric1, cut(char(')')), cut(alphanumeric1)),
+            cut(char('\n')),
+        ),
+        UnGraphMap::new(),
+        |mut graph, (a, b)| {
+            let a = graph.add_node(a);
+            let b = graph.add_node(b);
+            graph.add_edge(a, b, ());
+            graph
+        },
+    ))(input)
+}
+
+/// The name of a generated object, in digits and uppercase letters.
+fn base36(mut n: usize) -> String {
+    const DIGITS: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    let mut name = Vec::new();
+    loop {
+        name.push(char::from(DIGITS[n % 36]));
+        n /= 36;
+        if n == 0 {
+            break;
+        }
+    }
+    name.iter().rev().collect()
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 6;
+
+    type Input<'a> = UnGraphMap<&'a str, ()>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<UnGraphMap<&str, ()>, Box<dyn Error>> {
+        Ok(links(input).map_err(|e| Diagnostic::nom(input, e))?.1)
+    }
+
+    /// Total number of direct and indirect orbits
+    fn part_one(graph: &UnGraphMap<&str, ()>) -> Result<usize, Box<dyn Error>> {
+        let com = "COM";
+        let mut to_visit = vec![(com, 0)];
+        let mut acc = 0usize;
+        let mut visit_map = graph.visit_map();
+        visit_map.visit(com);
+        while let Some((node, i)) = to_visit.pop() {
+            acc += i;
+            for child in graph.neighbors(node).filter(|child| visit_map.visit(child)) {
+                to_visit.push((child, i + 1));
+            }
+        }
+        Ok(acc)
+    }
+
+    /// Orbital transfers between the objects orbited by YOU and SAN
+    fn part_two(graph: &UnGraphMap<&str, ()>) -> Result<usize, Box<dyn Error>> {
+        let sam = "SAN";
+        let you = "YOU";
+        let two = dijkstra(graph, you, Some(sam), |_| 1);
+        Ok(two.get(sam).ok_or("SAN isn't reachable from YOU")? - 2)
+    }
+
+    /// `size` objects with COM, YOU and SAN, in a deep tree like the real ones
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let names: Vec<_> = std::iter::once("COM".to_string())
+            .chain(
+                (36 * 36..)
+                    .map(base36)
+                    .filter(|name| !["COM", "YOU", "SAN"].contains(&name.as_str())),
+            )
+            .take(size.saturating_sub(2).max(1))
+            .collect();
+        let mut links: Vec<_> = (1..names.len())
+            .map(|i| {
+                // mostly around the objects just before
+                let center = i - 1 - rng.below(i.min(10));
+                format!("{}){}\n", names[center], names[i])
+            })
+            .collect();
+        for leaf in &["YOU", "SAN"] {
+            links.push(format!("{}){}\n", rng.choose(&names)?, leaf));
+        }
+        rng.shuffle(&mut links);
+        Some(links.concat())
+    }
+}
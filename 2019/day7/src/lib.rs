@@ -0,0 +1,72 @@
+use common::diagnostic::Diagnostic;
+use common::Solution;
+use std::error::Error;
+
+use intcode::scheduler::Scheduler;
+use intcode::{instructions, VM};
+
+use itertools::Itertools;
+use std::cmp::max;
+
+fn amplifier(instructions: &[i64], inputs: &[i64]) -> Result<i64, Box<dyn Error>> {
+    inputs
+        .iter()
+        .copied()
+        .try_fold(0, |a, b| -> Result<i64, Box<dyn Error>> {
+            let output = VM::new(instructions.to_vec(), vec![b, a]).run()?.output()?;
+            Ok(output)
+        })
+}
+
+fn amplifier_loop(instructions: &[i64], inputs: &[i64]) -> Result<i64, Box<dyn Error>> {
+    let mut scheduler = Scheduler::new(1);
+    let amplifiers: Vec<_> = inputs
+        .iter()
+        .map(|&phase| scheduler.spawn(VM::new(instructions.to_vec(), Some(phase))))
+        .collect();
+    for (i, &amplifier) in amplifiers.iter().enumerate() {
+        scheduler.connect(amplifier, amplifiers[(i + 1) % amplifiers.len()]);
+    }
+    scheduler.push(amplifiers[0], 0);
+    scheduler.run().map_err(|(_, e)| e)?;
+
+    let last = amplifiers[amplifiers.len() - 1];
+    Ok(*scheduler.outputs(last).last().ok_or("no signal")?)
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 7;
+
+    type Input<'a> = Vec<i64>;
+    type One = i64;
+    type Two = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>, Box<dyn Error>> {
+        Ok(instructions(input.trim())
+            .map_err(|e| Diagnostic::nom(input, e))?
+            .1)
+    }
+
+    fn part_one(instructions: &Vec<i64>) -> Result<i64, Box<dyn Error>> {
+        (0..5)
+            .permutations(5)
+            .map(|input| amplifier(instructions, &input))
+            .try_fold(0, |acc, x| -> Result<_, Box<dyn Error>> {
+                let x = x?;
+                Ok(max(x, acc))
+            })
+    }
+
+    fn part_two(instructions: &Vec<i64>) -> Result<i64, Box<dyn Error>> {
+        (5..10)
+            .permutations(5)
+            .map(|input| amplifier_loop(instructions, &input))
+            .try_fold(0, |acc, x| -> Result<_, Box<dyn Error>> {
+                let x = x?;
+                Ok(max(x, acc))
+            })
+    }
+}
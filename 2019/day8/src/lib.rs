@@ -0,0 +1,113 @@
+use common::diagnostic::Diagnostic;
+use common::grid::Grid;
+use common::image::{Palette, Rgb};
+use common::Solution;
+use std::error::Error;
+use std::fmt;
+
+use nom::{character::complete::*, combinator::*, error::ErrorKind, multi::*, IResult};
+
+#[derive(Debug, Copy, Clone)]
+pub enum Pixel {
+    Black,
+    White,
+    Transparent,
+}
+
+fn pixel(input: &str) -> IResult<&str, Pixel> {
+    let (input, c) = anychar(input)?;
+    match c {
+        '0' => Ok((input, Pixel::Black)),
+        '1' => Ok((input, Pixel::White)),
+        '2' => Ok((input, Pixel::Transparent)),
+        _ => Err(nom::Err::Error((input, ErrorKind::Tag))),
+    }
+}
+
+fn layers(input: &str, wide: usize, tall: usize) -> IResult<&str, Vec<Image>> {
+    all_consuming(many0(map_opt(count(pixel, wide * tall), move |image| {
+        Grid::from_vec(wide, image)
+    })))(input)
+}
+
+pub type Image = Grid<Pixel>;
+
+impl Palette for Pixel {
+    fn color(&self) -> Rgb {
+        match self {
+            Pixel::Black => Rgb::BLACK,
+            Pixel::White => Rgb::WHITE,
+            Pixel::Transparent => Rgb::GRAY,
+        }
+    }
+}
+
+impl fmt::Display for Pixel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let p = match self {
+            Pixel::Black => " ",
+            Pixel::White => "8",
+            Pixel::Transparent => " ",
+        };
+        write!(f, "{}", p)
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 8;
+
+    type Input<'a> = Vec<Image>;
+    type One = usize;
+    type Two = Image;
+
+    fn parse(input: &str) -> Result<Vec<Image>, Box<dyn Error>> {
+        let pixels = input.trim();
+        if let Some(i) = pixels.find(|c| !matches!(c, '0'..='2')) {
+            return Err(Diagnostic::new(input, &pixels[i..], "a pixel, 0, 1 or 2").into());
+        }
+        if !pixels.len().is_multiple_of(25 * 6) {
+            let expected = format!("a full layer of {} pixels", 25 * 6);
+            return Err(Diagnostic::new(input, &pixels[pixels.len()..], expected).into());
+        }
+        Ok(layers(pixels, 25, 6)
+            .map_err(|e| Diagnostic::nom(input, e))?
+            .1)
+    }
+
+    fn part_one(layers: &Vec<Image>) -> Result<usize, Box<dyn Error>> {
+        Ok(layers
+            .iter()
+            .map(|layer| {
+                layer.iter().fold((0, 0, 0), |(b, w, t), p| match p {
+                    Pixel::Black => (b + 1, w, t),
+                    Pixel::White => (b, w + 1, t),
+                    Pixel::Transparent => (b, w, t + 1),
+                })
+            })
+            .min_by_key(|(b, _, _)| *b)
+            .map(|(_, w, t)| w * t)
+            .ok_or("no layer")?)
+    }
+
+    fn part_two(layers: &Vec<Image>) -> Result<Image, Box<dyn Error>> {
+        let first = layers.first().ok_or("no layer")?;
+        let image = layers.iter().skip(1).fold(first.clone(), |mut acc, layer| {
+            for (acc, p) in acc.iter_mut().zip(layer.iter()) {
+                *acc = *match acc {
+                    Pixel::Black | Pixel::White => acc,
+                    Pixel::Transparent => p,
+                };
+            }
+            acc
+        });
+        Ok(image)
+    }
+
+    /// The decoded image
+    fn image(layers: &Vec<Image>) -> Result<Option<Grid<Rgb>>, Box<dyn Error>> {
+        Ok(Some(Self::part_two(layers)?.map(Palette::color)))
+    }
+}
@@ -0,0 +1,47 @@
+use common::diagnostic::Diagnostic;
+use common::Solution;
+use std::error::Error;
+
+use intcode::{instructions, State, VMError, VM};
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 9;
+
+    type Input<'a> = Vec<i64>;
+    type One = i64;
+    type Two = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>, Box<dyn Error>> {
+        Ok(instructions(input.trim())
+            .map_err(|e| Diagnostic::nom(input, e))?
+            .1)
+    }
+
+    /// BOOST keycode, the only output of the test mode
+    fn part_one(instructions: &Vec<i64>) -> Result<i64, Box<dyn Error>> {
+        let mut vm = VM::new(instructions.clone(), 1..2);
+        let mut output = 0;
+        loop {
+            match vm.run()? {
+                State::Halt => break Ok(output),
+                State::Output(o) => {
+                    if output != 0 {
+                        break Err(Box::new(VMError::Output));
+                    } else {
+                        output = o;
+                    }
+                }
+                State::Input => break Err(Box::new(VMError::Empty)),
+                State::Continue => unreachable!(),
+            }
+        }
+    }
+
+    /// Coordinates of the distress signal, in sensor boost mode
+    fn part_two(instructions: &Vec<i64>) -> Result<i64, Box<dyn Error>> {
+        Ok(VM::new(instructions.clone(), 2..3).run()?.output()?)
+    }
+}
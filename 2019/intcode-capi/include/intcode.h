@@ -0,0 +1,108 @@
+#ifndef INTCODE_H
+#define INTCODE_H
+
+/* Generated by cbindgen from src/lib.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Why `intcode_run` returned.
+ */
+typedef enum IntcodeStatus {
+  /**
+   * The program halted, running it again does nothing.
+   */
+  INTCODE_STATUS_HALT = 0,
+  /**
+   * The program produced an output, run again to continue.
+   */
+  INTCODE_STATUS_OUTPUT = 1,
+  /**
+   * The program needs an input, push one then run again.
+   */
+  INTCODE_STATUS_INPUT = 2,
+  /**
+   * The program did something invalid, the VM stays on the instruction that failed.
+   */
+  INTCODE_STATUS_ERROR = -1,
+} IntcodeStatus;
+
+/**
+ * An intcode VM, only used through a pointer.
+ */
+typedef struct IntcodeVm IntcodeVm;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Create a VM running a copy of the `len` values of `program`.
+ *
+ * Returns NULL if `program` is NULL and `len` isn't 0. The VM must be freed with `intcode_free`.
+ *
+ * # Safety
+ *
+ * `program` must point to `len` readable values.
+ */
+struct IntcodeVm *intcode_new(const int64_t *program, size_t len);
+
+/**
+ * Queue `value` as the next input of the program.
+ *
+ * # Safety
+ *
+ * `vm` must come from `intcode_new` and not be freed.
+ */
+void intcode_push_input(struct IntcodeVm *vm, int64_t value);
+
+/**
+ * Run the program until it outputs a value, needs an input or halts.
+ *
+ * When it returns `INTCODE_STATUS_OUTPUT` the value is written to `output` if it isn't NULL.
+ *
+ * # Safety
+ *
+ * `vm` must come from `intcode_new` and not be freed, `output` must be NULL or writable.
+ */
+enum IntcodeStatus intcode_run(struct IntcodeVm *vm, int64_t *output);
+
+/**
+ * Number of memory cells currently allocated by the program.
+ *
+ * # Safety
+ *
+ * `vm` must come from `intcode_new` and not be freed.
+ */
+size_t intcode_mem_len(const struct IntcodeVm *vm);
+
+/**
+ * Copy up to `len` memory cells starting at `address` into `buffer`.
+ *
+ * Returns the number of cells copied, `-1` if `vm` or `buffer` is NULL.
+ *
+ * # Safety
+ *
+ * `vm` must come from `intcode_new` and not be freed, `buffer` must point to `len` writable
+ * values.
+ */
+int intcode_read_memory(const struct IntcodeVm *vm, size_t address, int64_t *buffer, size_t len);
+
+/**
+ * Free a VM created by `intcode_new`, NULL is ignored.
+ *
+ * # Safety
+ *
+ * `vm` must come from `intcode_new` and not be freed already.
+ */
+void intcode_free(struct IntcodeVm *vm);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* INTCODE_H */
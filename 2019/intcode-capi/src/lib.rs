@@ -0,0 +1,225 @@
+//! C API of the intcode VM, the header is `include/intcode.h`.
+//!
+//! The build generates the header in `OUT_DIR`, a test checks the checked-in one is the same:
+//! copy it over `include/intcode.h` after a change of the API.
+//!
+//! A panic can't unwind into C, every function catches them and fails instead.
+
+use std::os::raw::c_int;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+
+use intcode::{State, VM};
+
+/// An intcode VM, only used through a pointer.
+pub struct IntcodeVm(VM);
+
+/// Why `intcode_run` returned.
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub enum IntcodeStatus {
+    /// The program halted, running it again does nothing.
+    Halt = 0,
+    /// The program produced an output, run again to continue.
+    Output = 1,
+    /// The program needs an input, push one then run again.
+    Input = 2,
+    /// The program did something invalid, the VM stays on the instruction that failed.
+    Error = -1,
+}
+
+/// `f()`, or `failed` if it panics.
+fn catch<T>(failed: T, f: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(failed)
+}
+
+/// Create a VM running a copy of the `len` values of `program`.
+///
+/// Returns NULL if `program` is NULL and `len` isn't 0. The VM must be freed with `intcode_free`.
+///
+/// # Safety
+///
+/// `program` must point to `len` readable values.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_new(program: *const i64, len: usize) -> *mut IntcodeVm {
+    catch(ptr::null_mut(), || {
+        let mem = if len == 0 {
+            Vec::new()
+        } else if program.is_null() {
+            return ptr::null_mut();
+        } else {
+            slice::from_raw_parts(program, len).to_vec()
+        };
+        Box::into_raw(Box::new(IntcodeVm(VM::new(mem, None))))
+    })
+}
+
+/// Queue `value` as the next input of the program.
+///
+/// # Safety
+///
+/// `vm` must come from `intcode_new` and not be freed.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_push_input(vm: *mut IntcodeVm, value: i64) {
+    catch((), || {
+        if let Some(IntcodeVm(vm)) = vm.as_mut() {
+            vm.inputs.push_back(value);
+        }
+    })
+}
+
+/// Run the program until it outputs a value, needs an input or halts.
+///
+/// When it returns `INTCODE_STATUS_OUTPUT` the value is written to `output` if it isn't NULL.
+///
+/// # Safety
+///
+/// `vm` must come from `intcode_new` and not be freed, `output` must be NULL or writable.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_run(vm: *mut IntcodeVm, output: *mut i64) -> IntcodeStatus {
+    catch(IntcodeStatus::Error, || {
+        let vm = match vm.as_mut() {
+            Some(IntcodeVm(vm)) => vm,
+            None => return IntcodeStatus::Error,
+        };
+        let state = loop {
+            // `tick` moves `p` before running the instruction, put it back if that panics
+            let p = vm.p;
+            match panic::catch_unwind(AssertUnwindSafe(|| vm.tick())) {
+                Ok(Ok(State::Continue)) => continue,
+                Ok(state) => break state,
+                Err(_) => {
+                    vm.p = p;
+                    return IntcodeStatus::Error;
+                }
+            }
+        };
+        match state {
+            Ok(State::Output(o)) => {
+                if !output.is_null() {
+                    *output = o;
+                }
+                IntcodeStatus::Output
+            }
+            Ok(State::Input) => IntcodeStatus::Input,
+            Ok(State::Halt) => IntcodeStatus::Halt,
+            Ok(State::Continue) | Err(_) => IntcodeStatus::Error,
+        }
+    })
+}
+
+/// Number of memory cells currently allocated by the program.
+///
+/// # Safety
+///
+/// `vm` must come from `intcode_new` and not be freed.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_mem_len(vm: *const IntcodeVm) -> usize {
+    catch(0, || vm.as_ref().map_or(0, |IntcodeVm(vm)| vm.mem.len()))
+}
+
+/// Copy up to `len` memory cells starting at `address` into `buffer`.
+///
+/// Returns the number of cells copied, `-1` if `vm` or `buffer` is NULL.
+///
+/// # Safety
+///
+/// `vm` must come from `intcode_new` and not be freed, `buffer` must point to `len` writable
+/// values.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_read_memory(
+    vm: *const IntcodeVm,
+    address: usize,
+    buffer: *mut i64,
+    len: usize,
+) -> c_int {
+    catch(-1, || {
+        let vm = match vm.as_ref() {
+            Some(IntcodeVm(vm)) if !buffer.is_null() => vm,
+            _ => return -1,
+        };
+        let mem = vm.mem.get(address..).unwrap_or(&[]);
+        let count = mem.len().min(len).min(c_int::MAX as usize);
+        ptr::copy_nonoverlapping(mem.as_ptr(), buffer, count);
+        count as c_int
+    })
+}
+
+/// Free a VM created by `intcode_new`, NULL is ignored.
+///
+/// # Safety
+///
+/// `vm` must come from `intcode_new` and not be freed already.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_free(vm: *mut IntcodeVm) {
+    catch((), || {
+        if !vm.is_null() {
+            drop(Box::from_raw(vm));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sum() {
+        let program = [3, 11, 3, 12, 1, 11, 12, 13, 4, 13, 99, 0, 0, 0];
+        unsafe {
+            let vm = intcode_new(program.as_ptr(), program.len());
+            assert!(!vm.is_null());
+            let mut output = 0;
+            assert_eq!(intcode_run(vm, &mut output), IntcodeStatus::Input);
+            intcode_push_input(vm, 40);
+            intcode_push_input(vm, 2);
+            assert_eq!(intcode_run(vm, &mut output), IntcodeStatus::Output);
+            assert_eq!(output, 42);
+            assert_eq!(intcode_run(vm, &mut output), IntcodeStatus::Halt);
+
+            let mut mem = [0; 4];
+            assert_eq!(intcode_mem_len(vm), program.len());
+            assert_eq!(intcode_read_memory(vm, 11, mem.as_mut_ptr(), mem.len()), 3);
+            assert_eq!(mem, [40, 2, 42, 0]);
+            intcode_free(vm);
+        }
+    }
+
+    #[test]
+    fn panic() {
+        // the write at i64::MAX can't grow the memory, a capacity overflow panic
+        let program = [1101, 1, 1, i64::MAX, 99];
+        unsafe {
+            let vm = intcode_new(program.as_ptr(), program.len());
+            assert_eq!(intcode_run(vm, ptr::null_mut()), IntcodeStatus::Error);
+            assert_eq!((*vm).0.p, 0);
+            assert_eq!(intcode_run(vm, ptr::null_mut()), IntcodeStatus::Error);
+            assert_eq!((*vm).0.p, 0);
+            intcode_free(vm);
+        }
+    }
+
+    #[test]
+    fn error() {
+        // the relative base overflows
+        let program = [109, i64::MAX, 109, 1, 99];
+        unsafe {
+            let vm = intcode_new(program.as_ptr(), program.len());
+            assert_eq!(intcode_run(vm, ptr::null_mut()), IntcodeStatus::Error);
+            assert_eq!((*vm).0.p, 2);
+            assert_eq!(intcode_run(vm, ptr::null_mut()), IntcodeStatus::Error);
+            assert_eq!((*vm).0.p, 2);
+            intcode_free(vm);
+        }
+    }
+
+    #[test]
+    fn header() {
+        assert_eq!(
+            include_str!(concat!(env!("OUT_DIR"), "/intcode.h")),
+            include_str!("../include/intcode.h"),
+            "the API changed, copy the generated header to include/intcode.h"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::error::Error;
+
+use intcode::{instructions, State, VM};
+
+const USAGE: &str =
+    "usage: intcode-dump PROGRAM [--set ADDRESS=VALUE]... [--input VALUE]... [--diff]
+
+Run PROGRAM until it halts then dump its memory, with --diff only list the cells
+that changed since the start, after the --set patches were applied.";
+
+struct Options {
+    program: String,
+    patches: Vec<(usize, i64)>,
+    inputs: Vec<i64>,
+    diff: bool,
+}
+
+/// Print `error` then the usage to stderr and exit.
+fn misuse(error: Option<&str>) -> ! {
+    if let Some(error) = error {
+        eprintln!("{}", error);
+    }
+    eprintln!("{}", USAGE);
+    std::process::exit(2);
+}
+
+fn parse_args() -> Result<Options, Box<dyn Error>> {
+    let mut args = std::env::args().skip(1);
+    let mut program = None;
+    let mut patches = Vec::new();
+    let mut inputs = Vec::new();
+    let mut diff = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--set" => {
+                let patch = args.next().ok_or("--set needs ADDRESS=VALUE")?;
+                let mut split = patch.splitn(2, '=');
+                let address = split.next().unwrap_or("").parse()?;
+                let value = split.next().ok_or("--set needs ADDRESS=VALUE")?.parse()?;
+                patches.push((address, value));
+            }
+            "--input" => inputs.push(args.next().ok_or("--input needs VALUE")?.parse()?),
+            "--diff" => diff = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            _ if program.is_none() => program = Some(arg),
+            _ => misuse(Some(&format!("unexpected argument {}", arg))),
+        }
+    }
+    Ok(Options {
+        program: program.unwrap_or_else(|| misuse(None)),
+        patches,
+        inputs,
+        diff,
+    })
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let options = parse_args()?;
+    let source = std::fs::read_to_string(&options.program)?;
+    let mem = instructions(source.trim())
+        .map_err(|e| format!("{}: {:?}", options.program, e))?
+        .1;
+
+    let mut vm = VM::new(mem, options.inputs);
+    for (address, value) in options.patches {
+        vm.put(address, value);
+    }
+    let before = vm.snapshot();
+    while let State::Output(o) = vm.run()? {
+        println!("Output: {}", o);
+    }
+    let after = vm.snapshot();
+
+    if options.diff {
+        for change in before.diff(&after) {
+            println!("{}", change);
+        }
+    } else {
+        print!("{}", after);
+    }
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use std::error::Error;
+
+use intcode::decompile::decompile;
+use intcode::io::{Format, Io};
+use intcode::search::{Goal, Search};
+use intcode::{instructions, VM};
+
+const USAGE: &str = "usage: intcode [OPTIONS] PROGRAM [INPUT]...
+
+Run the intcode PROGRAM, INPUTs are given to the program first then every time it
+needs more a line is read from stdin.
+
+Options:
+    --ascii-input       read stdin as text, every byte is an input
+    --ascii-output      print outputs as text, values out of ASCII as numbers
+    --ascii             both --ascii-input and --ascii-output
+    --max-memory CELLS  fail instead of growing the memory beyond CELLS
+    --trace             print every instruction to stderr before executing it
+    --decompile         print the program as pseudocode instead of running it
+    --taint             print to stderr which inputs every output and branch depends on
+    --watch-code        print to stderr the writes of the program into its own code
+    --strict-code       fail on the first write of the program into its own code
+    --find-address ADDR search inputs reaching the instruction at ADDR instead of running
+    --find-output VALUE search inputs making the program output VALUE instead of running";
+
+struct Options {
+    program: String,
+    inputs: Vec<i64>,
+    io: Io,
+    max_memory: Option<usize>,
+    decompile: bool,
+    taint: bool,
+    watch_code: Option<bool>,
+    find: Option<Goal>,
+}
+
+/// Print the usage to stderr and exit.
+fn misuse() -> ! {
+    eprintln!("{}", USAGE);
+    std::process::exit(2);
+}
+
+fn parse_args() -> Result<Options, Box<dyn Error>> {
+    let mut args = std::env::args().skip(1);
+    let mut program = None;
+    let mut inputs = Vec::new();
+    let mut io = Io::default();
+    let mut max_memory = None;
+    let mut decompile = false;
+    let mut taint = false;
+    let mut watch_code = None;
+    let mut find = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--ascii-input" => io.input = Format::Ascii,
+            "--ascii-output" => io.output = Format::Ascii,
+            "--ascii" => {
+                io.input = Format::Ascii;
+                io.output = Format::Ascii;
+            }
+            "--max-memory" => {
+                max_memory = Some(args.next().ok_or("--max-memory needs CELLS")?.parse()?)
+            }
+            "--trace" => io.trace = true,
+            "--decompile" => decompile = true,
+            "--taint" => taint = true,
+            "--watch-code" => watch_code = Some(watch_code.unwrap_or(false)),
+            "--strict-code" => watch_code = Some(true),
+            "--find-address" => {
+                find = Some(Goal::Address(
+                    args.next().ok_or("--find-address needs ADDR")?.parse()?,
+                ))
+            }
+            "--find-output" => {
+                find = Some(Goal::Output(
+                    args.next().ok_or("--find-output needs VALUE")?.parse()?,
+                ))
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            _ if program.is_none() => program = Some(arg),
+            _ => inputs.push(
+                arg.parse()
+                    .map_err(|e| format!("invalid input {}: {}", arg, e))?,
+            ),
+        }
+    }
+    Ok(Options {
+        program: program.unwrap_or_else(|| misuse()),
+        inputs,
+        io,
+        max_memory,
+        decompile,
+        taint,
+        watch_code,
+        find,
+    })
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let options = parse_args()?;
+    let source = std::fs::read_to_string(&options.program)?;
+    let mem = instructions(source.trim())
+        .map_err(|e| format!("{}: {:?}", options.program, e))?
+        .1;
+
+    if options.decompile {
+        print!("{}", decompile(&mem));
+        return Ok(());
+    }
+    if let Some(goal) = options.find {
+        let inputs = Search::new(mem).find(goal).ok_or("no inputs found")?;
+        let inputs: Vec<_> = inputs.iter().map(i64::to_string).collect();
+        println!("{}", inputs.join(" "));
+        return Ok(());
+    }
+    let mut vm = VM::new(mem, options.inputs);
+    if let Some(cells) = options.max_memory {
+        vm.limit_memory(cells);
+    }
+    if options.taint {
+        vm.enable_taint();
+    }
+    if let Some(strict) = options.watch_code {
+        vm.watch_code(strict);
+    }
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    let result = options.io.run(&mut vm, stdin.lock(), stdout.lock());
+
+    if let Some(selfmod) = vm.self_modification() {
+        for write in selfmod.writes() {
+            eprintln!("{}", write);
+        }
+    }
+    result?;
+
+    if let Some(taint) = vm.taint() {
+        for (i, labels) in taint.outputs().iter().enumerate() {
+            eprintln!("output {} depends on inputs {}", i, labels);
+        }
+        for branch in taint.branches() {
+            eprintln!(
+                "{:05}: jump {}taken, depends on inputs {}",
+                branch.address,
+                if branch.taken { "" } else { "not " },
+                branch.labels
+            );
+        }
+    }
+    Ok(())
+}
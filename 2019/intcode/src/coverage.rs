@@ -0,0 +1,114 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+const EXECUTED: u8 = 1;
+const READ: u8 = 2;
+const WRITTEN: u8 = 4;
+
+/// Cells per row when rendering a coverage map.
+const WIDTH: usize = 64;
+
+/// Records how every address of the VM memory was used.
+#[derive(Debug, Clone, Default)]
+pub struct Coverage {
+    cells: Vec<u8>,
+}
+
+impl Coverage {
+    /// `len` is the size of the program, the map grows if the program uses more memory.
+    pub fn new(len: usize) -> Self {
+        Self {
+            cells: vec![0; len],
+        }
+    }
+
+    fn mark(&mut self, index: usize, flag: u8) {
+        if self.cells.len() < index + 1 {
+            self.cells.resize(index + 1, 0);
+        }
+        self.cells[index] |= flag;
+    }
+
+    pub(crate) fn execute(&mut self, range: Range<usize>) {
+        for index in range {
+            self.mark(index, EXECUTED);
+        }
+    }
+
+    pub(crate) fn read(&mut self, index: usize) {
+        self.mark(index, READ);
+    }
+
+    pub(crate) fn write(&mut self, index: usize) {
+        self.mark(index, WRITTEN);
+    }
+
+    fn is(&self, index: usize, flag: u8) -> bool {
+        self.cells.get(index).is_some_and(|cell| cell & flag != 0)
+    }
+
+    pub fn is_executed(&self, index: usize) -> bool {
+        self.is(index, EXECUTED)
+    }
+
+    pub fn is_read(&self, index: usize) -> bool {
+        self.is(index, READ)
+    }
+
+    pub fn is_written(&self, index: usize) -> bool {
+        self.is(index, WRITTEN)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Addresses executed as instructions.
+    pub fn executed(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len()).filter(move |&index| self.is_executed(index))
+    }
+
+    /// Addresses never touched.
+    pub fn unused(&self) -> impl Iterator<Item = usize> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| **cell == 0)
+            .map(|(index, _)| index)
+    }
+}
+
+/// One character per address: `.` unused, `X` executed, `r` read, `w` written, `b` read and
+/// written, `!` executed and written.
+impl fmt::Display for Coverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (row, cells) in self.cells.chunks(WIDTH).enumerate() {
+            write!(f, "{:05} ", row * WIDTH)?;
+            for cell in cells {
+                let c = if cell & EXECUTED != 0 {
+                    if cell & WRITTEN != 0 {
+                        '!'
+                    } else {
+                        'X'
+                    }
+                } else {
+                    match (cell & READ != 0, cell & WRITTEN != 0) {
+                        (false, false) => '.',
+                        (true, false) => 'r',
+                        (false, true) => 'w',
+                        (true, true) => 'b',
+                    }
+                };
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
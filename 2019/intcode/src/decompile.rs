@@ -0,0 +1,734 @@
+//! Recover structured pseudocode from a program without running it.
+//!
+//! Only the instruction set of the 2019 puzzles is understood, other opcodes are printed as
+//! calls named after them. Functions follow the convention of the puzzles: the caller stores
+//! the return address in `[rb+0]` and jumps to the function, which moves `rb` past its frame
+//! with `arb N`, moves it back with `arb -N` and jumps to `[rb+0]`.
+//!
+//! Relative cells are named after their offset from `rb` at the entry of the function: `ret`
+//! is the return address, `local1` and up start with the arguments. In `main` `rb` starts at 0,
+//! so they are the same `mem[N]` as the positional cells. Once `rb` depends on run time values
+//! they are written `mem[rb+N]`, and every change of `rb` in the function is printed.
+use alloc::collections::btree_map::Entry;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+use crate::{Instruction, Opcodes, Operand};
+
+/// What an instruction does to the control flow, as far as it is known without running it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Flow {
+    Next,
+    /// A conditional jump, the VM continues at `next` when it isn't taken.
+    Branch(usize),
+    Goto(usize),
+    /// Two instructions storing the return address then jumping to the function.
+    Call(usize),
+    /// A jump to the return address.
+    Return,
+    /// A jump to an address only known at run time.
+    Indirect,
+    Halt,
+    /// Nothing valid can be decoded there.
+    Invalid(i64),
+}
+
+struct Step {
+    instruction: Option<Instruction>,
+    flow: Flow,
+    next: usize,
+    /// Offset of `rb` since the entry of the function, `None` once it depends on run time values.
+    depth: Option<i64>,
+}
+
+struct Function {
+    entry: usize,
+    steps: BTreeMap<usize, Step>,
+}
+
+/// The functions of a program, `main` starts at address 0.
+pub struct Pseudocode {
+    functions: Vec<Function>,
+}
+
+impl Pseudocode {
+    /// Entry addresses of the functions found, `main` first.
+    pub fn functions(&self) -> impl Iterator<Item = usize> + '_ {
+        self.functions.iter().map(|function| function.entry)
+    }
+}
+
+/// Decompile the program in `mem`, starting at address 0.
+pub fn decompile(mem: &[i64]) -> Pseudocode {
+    let opcodes = Opcodes::default();
+    let mut todo = vec![0];
+    let mut functions = BTreeMap::new();
+    while let Some(entry) = todo.pop() {
+        if let Entry::Vacant(vacant) = functions.entry(entry) {
+            let function = vacant.insert(explore(mem, &opcodes, entry));
+            for step in function.steps.values() {
+                if let Flow::Call(callee) = step.flow {
+                    todo.push(callee);
+                }
+            }
+        }
+    }
+    Pseudocode {
+        functions: functions.into_values().collect(),
+    }
+}
+
+fn explore(mem: &[i64], opcodes: &Opcodes, entry: usize) -> Function {
+    let mut steps = BTreeMap::new();
+    let mut todo = vec![(entry, Some(0))];
+    while let Some((address, depth)) = todo.pop() {
+        if steps.contains_key(&address) {
+            continue;
+        }
+        let step = analyse(mem, opcodes, address, depth);
+        let depth = match step.instruction {
+            Some(Instruction {
+                opcode: 9,
+                operands: [Operand::Immediate(offset), ..],
+                ..
+            }) => depth.and_then(|depth| depth.checked_add(offset)),
+            Some(Instruction { opcode: 9, .. }) => None,
+            _ => depth,
+        };
+        match step.flow {
+            Flow::Next | Flow::Call(_) => todo.push((step.next, depth)),
+            Flow::Branch(target) => {
+                todo.push((step.next, depth));
+                todo.push((target, depth));
+            }
+            Flow::Goto(target) => todo.push((target, depth)),
+            Flow::Return | Flow::Indirect if conditional(&step) => todo.push((step.next, depth)),
+            Flow::Return | Flow::Indirect | Flow::Halt | Flow::Invalid(_) => {}
+        }
+        steps.insert(address, step);
+    }
+    Function { entry, steps }
+}
+
+fn analyse(mem: &[i64], opcodes: &Opcodes, address: usize, depth: Option<i64>) -> Step {
+    let instruction = match opcodes.decode(mem, address) {
+        Ok(instruction) => instruction,
+        Err(_) => {
+            return Step {
+                instruction: None,
+                flow: Flow::Invalid(mem.get(address).copied().unwrap_or(0)),
+                next: address + 1,
+                depth,
+            }
+        }
+    };
+    let mut next = instruction.next();
+    let flow = match instruction.opcode {
+        5 | 6 => match (taken(&instruction), instruction.operands[1]) {
+            (Some(false), _) => Flow::Next,
+            (taken, Operand::Immediate(target)) => match usize::try_from(target) {
+                Ok(target) if taken.is_some() => Flow::Goto(target),
+                Ok(target) => Flow::Branch(target),
+                Err(_) => Flow::Invalid(target),
+            },
+            (_, Operand::Relative(offset))
+                if depth.and_then(|depth| depth.checked_add(offset)) == Some(0) =>
+            {
+                Flow::Return
+            }
+            _ => Flow::Indirect,
+        },
+        99 => Flow::Halt,
+        _ => match call(mem, opcodes, &instruction) {
+            Some((callee, ret)) => {
+                next = ret;
+                Flow::Call(callee)
+            }
+            None => Flow::Next,
+        },
+    };
+    Step {
+        instruction: Some(instruction),
+        flow,
+        next,
+        depth,
+    }
+}
+
+/// Whether a jump is always or never taken, `None` if it depends on run time values.
+fn taken(instruction: &Instruction) -> Option<bool> {
+    match instruction.operands[0] {
+        Operand::Immediate(value) => Some((value != 0) == (instruction.opcode == 5)),
+        _ => None,
+    }
+}
+
+fn conditional(step: &Step) -> bool {
+    step.instruction
+        .as_ref()
+        .is_some_and(|instruction| taken(instruction).is_none())
+}
+
+/// `add RET, 0, [rb+0]` followed by `jnz 1, FUNCTION`, where `RET` is the address after the
+/// jump. Gives the function and the return address.
+fn call(mem: &[i64], opcodes: &Opcodes, store: &Instruction) -> Option<(usize, usize)> {
+    let ret = match (store.opcode, store.operands) {
+        (1, [Operand::Immediate(a), Operand::Immediate(b), Operand::Relative(0)]) => {
+            a.checked_add(b)?
+        }
+        (2, [Operand::Immediate(a), Operand::Immediate(b), Operand::Relative(0)]) => {
+            a.checked_mul(b)?
+        }
+        _ => return None,
+    };
+    let jump = opcodes.decode(mem, store.next()).ok()?;
+    match (jump.opcode, taken(&jump), jump.operands[1]) {
+        (5, Some(true), Operand::Immediate(callee))
+        | (6, Some(true), Operand::Immediate(callee))
+            if ret == jump.next() as i64 =>
+        {
+            Some((usize::try_from(callee).ok()?, jump.next()))
+        }
+        _ => None,
+    }
+}
+
+/// A conditional jump, with the comparison computing its condition when there is one.
+struct Test {
+    /// Address of the jump.
+    branch: usize,
+    target: usize,
+    next: usize,
+    lhs: String,
+    op: &'static str,
+    rhs: String,
+    /// The cell the comparison stores its result in, the assignment stays in the condition.
+    store: Option<String>,
+    /// Whether the jump is taken when `lhs op rhs` holds.
+    when: bool,
+}
+
+impl Test {
+    fn taken(&self) -> String {
+        self.condition(self.when)
+    }
+
+    fn not_taken(&self) -> String {
+        self.condition(!self.when)
+    }
+
+    fn condition(&self, holds: bool) -> String {
+        match (&self.store, holds) {
+            (None, true) => format!("{} {} {}", self.lhs, self.op, self.rhs),
+            (None, false) => format!("{} {} {}", self.lhs, negate(self.op), self.rhs),
+            (Some(store), true) => format!("{} = {} {} {}", store, self.lhs, self.op, self.rhs),
+            (Some(store), false) => {
+                format!("!({} = {} {} {})", store, self.lhs, self.op, self.rhs)
+            }
+        }
+    }
+}
+
+fn negate(op: &'static str) -> &'static str {
+    match op {
+        "==" => "!=",
+        "!=" => "==",
+        "<" => ">=",
+        _ => "<",
+    }
+}
+
+struct Line {
+    address: usize,
+    indent: usize,
+    text: String,
+}
+
+struct Emitter<'a> {
+    function: &'a Function,
+    /// Whether `rb` depends on run time values somewhere in the function.
+    dynamic: bool,
+    /// Loop headers and the address of the last jump back to them.
+    loops: BTreeMap<usize, usize>,
+    entered: BTreeSet<usize>,
+    labels: BTreeSet<usize>,
+    lines: Vec<Line>,
+}
+
+impl<'a> Emitter<'a> {
+    fn new(function: &'a Function) -> Self {
+        let mut loops = BTreeMap::new();
+        for (&address, step) in &function.steps {
+            if let Flow::Branch(target) | Flow::Goto(target) = step.flow {
+                if target <= address && function.steps.contains_key(&target) {
+                    let latch = loops.entry(target).or_insert(address);
+                    *latch = address.max(*latch);
+                }
+            }
+        }
+        Self {
+            function,
+            dynamic: function.steps.values().any(|step| step.depth.is_none()),
+            loops,
+            entered: BTreeSet::new(),
+            labels: BTreeSet::new(),
+            lines: Vec::new(),
+        }
+    }
+
+    fn line(&mut self, address: usize, indent: usize, text: String) {
+        self.lines.push(Line {
+            address,
+            indent,
+            text,
+        });
+    }
+
+    fn operand(&self, operand: Operand, depth: Option<i64>) -> String {
+        match (operand, depth) {
+            (Operand::Position(address), _) => format!("mem[{}]", address),
+            (Operand::Immediate(value), _) => value.to_string(),
+            (Operand::Relative(offset), Some(depth)) if depth.checked_add(offset).is_some() => {
+                match depth + offset {
+                    slot if self.function.entry == 0 => format!("mem[{}]", slot),
+                    0 => "ret".to_string(),
+                    slot if slot < 0 => format!("caller{}", slot.unsigned_abs()),
+                    slot => format!("local{}", slot),
+                }
+            }
+            (Operand::Relative(offset), _) if offset < 0 => {
+                format!("mem[rb-{}]", offset.unsigned_abs())
+            }
+            (Operand::Relative(offset), _) => format!("mem[rb+{}]", offset),
+        }
+    }
+
+    /// The conditional jump at `address`, or the comparison at `address` feeding the
+    /// conditional jump that follows it.
+    fn test(&self, address: usize) -> Option<Test> {
+        let step = &self.function.steps[&address];
+        let instruction = step.instruction?;
+        let operand = |i| self.operand(instruction.operands[i], step.depth);
+        match (instruction.opcode, step.flow) {
+            (5, Flow::Branch(target)) | (6, Flow::Branch(target)) => Some(Test {
+                branch: address,
+                target,
+                next: step.next,
+                lhs: operand(0),
+                op: "!=",
+                rhs: "0".to_string(),
+                store: None,
+                when: instruction.opcode == 5,
+            }),
+            (7, Flow::Next) | (8, Flow::Next) => {
+                let jump = self.function.steps.get(&step.next)?;
+                match (jump.instruction, jump.flow) {
+                    (Some(condition), Flow::Branch(target))
+                        if condition.operands[0] == instruction.operands[2]
+                            && jump.depth == step.depth =>
+                    {
+                        Some(Test {
+                            branch: step.next,
+                            target,
+                            next: jump.next,
+                            lhs: operand(0),
+                            op: if instruction.opcode == 7 { "<" } else { "==" },
+                            rhs: operand(1),
+                            store: Some(operand(2)),
+                            when: condition.opcode == 5,
+                        })
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn emit(&mut self, from: usize, to: usize, indent: usize) {
+        let mut cursor = from;
+        // a step may overlap `to` when a jump lands inside an instruction
+        while cursor < to {
+            let address = match self.function.steps.range(cursor..to).next() {
+                Some((&address, _)) => address,
+                None => break,
+            };
+            cursor = match self.loops.get(&address) {
+                Some(&latch) if latch < to && self.entered.insert(address) => {
+                    self.emit_loop(address, latch, indent)
+                }
+                _ => self.emit_step(address, to, indent),
+            };
+        }
+    }
+
+    fn emit_loop(&mut self, header: usize, latch: usize, indent: usize) -> usize {
+        let steps = &self.function.steps;
+        let end = steps[&latch].next;
+        if let Flow::Branch(_) = steps[&latch].flow {
+            // the comparison computing the condition belongs to the `while`
+            let compare = steps
+                .range(header..latch)
+                .next_back()
+                .and_then(|(&address, _)| {
+                    self.test(address)
+                        .filter(|test| test.branch == latch)
+                        .map(|test| (address, test))
+                });
+            let (body_end, test) = match compare {
+                Some(compare) => compare,
+                None => (
+                    latch,
+                    self.test(latch).expect("a conditional jump is a test"),
+                ),
+            };
+            self.line(header, indent, "do {".to_string());
+            self.emit(header, body_end, indent + 1);
+            self.line(latch, indent, format!("}} while ({});", test.taken()));
+        } else {
+            match self.test(header) {
+                Some(test) if test.target == end => {
+                    self.line(header, indent, format!("while ({}) {{", test.not_taken()));
+                    self.emit(test.next, latch, indent + 1);
+                }
+                _ => {
+                    self.line(header, indent, "loop {".to_string());
+                    self.emit(header, latch, indent + 1);
+                }
+            }
+            self.line(latch, indent, "}".to_string());
+        }
+        end
+    }
+
+    fn emit_step(&mut self, address: usize, to: usize, indent: usize) -> usize {
+        if let Some(test) = self.test(address) {
+            return self.emit_if(address, test, to, indent);
+        }
+        let step = &self.function.steps[&address];
+        let depth = step.depth;
+        let next = step.next;
+        let guard = |text: String| match (step.instruction, conditional(step)) {
+            (Some(instruction), true) => format!(
+                "if ({} {} 0) {}",
+                self.operand(instruction.operands[0], depth),
+                if instruction.opcode == 5 { "!=" } else { "==" },
+                text
+            ),
+            _ => text,
+        };
+        let text = match step.flow {
+            Flow::Goto(target) => {
+                if self
+                    .function
+                    .steps
+                    .range(address + 1..)
+                    .next()
+                    .map(|(&a, _)| a)
+                    == Some(target)
+                {
+                    return next;
+                }
+                self.labels.insert(target);
+                format!("goto L{};", target)
+            }
+            Flow::Call(callee) => match depth {
+                Some(_) => format!(
+                    "f{}();  // frame at {}",
+                    callee,
+                    self.operand(Operand::Relative(0), depth)
+                ),
+                None => format!("f{}();", callee),
+            },
+            Flow::Return => guard("return;".to_string()),
+            Flow::Indirect => {
+                let target = step.instruction.map(|instruction| instruction.operands[1]);
+                guard(format!("goto *{};", self.operand(target.unwrap(), depth)))
+            }
+            Flow::Halt => "halt();".to_string(),
+            Flow::Invalid(value) => format!("invalid({});", value),
+            Flow::Next | Flow::Branch(_) => match step.instruction {
+                Some(instruction) => match self.statement(&instruction, depth) {
+                    Some(text) => text,
+                    None => return next,
+                },
+                None => return next,
+            },
+        };
+        self.line(address, indent, text);
+        let step = &self.function.steps[&address];
+        let falls = match step.flow {
+            Flow::Goto(_) | Flow::Halt | Flow::Invalid(_) => false,
+            Flow::Return | Flow::Indirect => conditional(step),
+            Flow::Next | Flow::Branch(_) | Flow::Call(_) => true,
+        };
+        self.skip(address, next, to, falls, indent)
+    }
+
+    /// Where to continue after the instruction at `address`: a step a jump lands inside it,
+    /// after a `goto` to `next` if the VM falls through, or `next`.
+    fn skip(
+        &mut self,
+        address: usize,
+        next: usize,
+        to: usize,
+        falls: bool,
+        indent: usize,
+    ) -> usize {
+        match self.function.steps.range(address + 1..next.min(to)).next() {
+            Some((&inside, _)) => {
+                if falls {
+                    self.labels.insert(next);
+                    self.line(address, indent, format!("goto L{};", next));
+                }
+                inside
+            }
+            None => next,
+        }
+    }
+
+    fn emit_if(&mut self, address: usize, test: Test, to: usize, indent: usize) -> usize {
+        // a target before `next` or inside an instruction of the block can't be an `if`
+        let overlaps = self
+            .function
+            .steps
+            .range(test.next.min(test.target)..test.target)
+            .next_back()
+            .is_some_and(|(_, step)| step.next > test.target);
+        if test.target <= test.branch || test.target < test.next || overlaps || test.target > to {
+            self.labels.insert(test.target);
+            self.line(
+                address,
+                indent,
+                format!("if ({}) goto L{};", test.taken(), test.target),
+            );
+            return self.skip(test.branch, test.next, to, true, indent);
+        }
+        let mark = self.lines.len();
+        self.line(address, indent, format!("if ({}) {{", test.not_taken()));
+        let last = self
+            .function
+            .steps
+            .range(test.next..test.target)
+            .next_back()
+            .map(|(&a, step)| (a, step.flow));
+        let end = match last {
+            Some((last, Flow::Goto(end))) if end > test.target && end <= to => {
+                self.emit(test.next, last, indent + 1);
+                let otherwise = self.lines.len();
+                self.line(last, indent, "} else {".to_string());
+                self.emit(test.target, end, indent + 1);
+                if self.lines.len() == otherwise + 1 {
+                    self.lines.pop();
+                } else if otherwise == mark + 1 {
+                    // only the else branch does something
+                    self.lines.remove(otherwise);
+                    self.lines[mark].text = format!("if ({}) {{", test.taken());
+                }
+                end
+            }
+            _ => {
+                self.emit(test.next, test.target, indent + 1);
+                test.target
+            }
+        };
+        self.line(end, indent, "}".to_string());
+        end
+    }
+
+    fn statement(&self, instruction: &Instruction, depth: Option<i64>) -> Option<String> {
+        let operand = |i| self.operand(instruction.operands[i], depth);
+        let text = match (instruction.opcode, instruction.operands) {
+            (1, [Operand::Immediate(0), src, dst])
+            | (1, [src, Operand::Immediate(0), dst])
+            | (2, [Operand::Immediate(1), src, dst])
+            | (2, [src, Operand::Immediate(1), dst])
+                if src == dst =>
+            {
+                return None
+            }
+            (1, [Operand::Immediate(0), _, _]) => format!("{} = {};", operand(2), operand(1)),
+            (1, [_, Operand::Immediate(0), _]) => format!("{} = {};", operand(2), operand(0)),
+            (1, [_, Operand::Immediate(value), _]) if value < 0 => {
+                format!(
+                    "{} = {} - {};",
+                    operand(2),
+                    operand(0),
+                    value.unsigned_abs()
+                )
+            }
+            (1, _) => format!("{} = {} + {};", operand(2), operand(0), operand(1)),
+            (2, [Operand::Immediate(1), _, _]) => format!("{} = {};", operand(2), operand(1)),
+            (2, [_, Operand::Immediate(1), _]) => format!("{} = {};", operand(2), operand(0)),
+            (2, _) => format!("{} = {} * {};", operand(2), operand(0), operand(1)),
+            (3, _) => format!("{} = input();", operand(0)),
+            (4, _) => format!("output({});", operand(0)),
+            (7, _) => format!("{} = {} < {};", operand(2), operand(0), operand(1)),
+            (8, _) => format!("{} = {} == {};", operand(2), operand(0), operand(1)),
+            // while the depth is known everywhere the names of the locals already follow `rb`
+            (9, [Operand::Immediate(_), _, _]) if !self.dynamic => return None,
+            (9, [Operand::Immediate(value), _, _]) if value < 0 => {
+                format!("rb -= {};", value.unsigned_abs())
+            }
+            (9, [Operand::Immediate(value), _, _]) => format!("rb += {};", value),
+            (9, _) => format!("rb += {};", operand(0)),
+            _ => {
+                let operands: Vec<_> = (0..instruction.params.len()).map(operand).collect();
+                format!("{}({});", instruction.name, operands.join(", "))
+            }
+        };
+        Some(text)
+    }
+
+    fn finish(self) -> Vec<Line> {
+        let mut lines = Vec::with_capacity(self.lines.len() + self.labels.len());
+        let mut labels = self.labels;
+        for line in self.lines {
+            let placed: Vec<_> = labels.range(..=line.address).copied().collect();
+            for label in placed {
+                labels.remove(&label);
+                lines.push(Line {
+                    address: label,
+                    indent: line.indent,
+                    text: format!("L{}:", label),
+                });
+            }
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+impl fmt::Display for Pseudocode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, function) in self.functions.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            match function.entry {
+                0 => writeln!(f, "fn main() {{")?,
+                entry => writeln!(f, "fn f{}() {{", entry)?,
+            }
+            let mut emitter = Emitter::new(function);
+            emitter.emit(0, usize::MAX, 1);
+            for line in emitter.finish() {
+                writeln!(f, "{:width$}{}", "", line.text, width = line.indent * 4)?;
+            }
+            writeln!(f, "}}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{State, VM};
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    // mem[101] = input(), count it up to 10, double it in a function then output it
+    const PROGRAM: [i64; 39] = [
+        109, 100, 203, 1, 21207, 1, 10, 2, 1206, 2, 18, 21201, 1, 1, 1, 1105, 1, 4, 21101, 25, 0,
+        0, 1105, 1, 28, 204, 1, 99, 109, 2, 21202, -1, 2, -1, 109, -2, 2105, 1, 0,
+    ];
+
+    #[test]
+    fn structured() {
+        let mut vm = VM::new(PROGRAM.to_vec(), Some(3));
+        assert_eq!(vm.run().unwrap(), State::Output(20));
+        assert_eq!(vm.run().unwrap(), State::Halt);
+
+        let pseudocode = decompile(&PROGRAM);
+        assert_eq!(pseudocode.functions().collect::<Vec<_>>(), [0, 28]);
+        assert_eq!(
+            pseudocode.to_string(),
+            "fn main() {
+    mem[101] = input();
+    while (mem[102] = mem[101] < 10) {
+        mem[101] = mem[101] + 1;
+    }
+    f28();  // frame at mem[100]
+    output(mem[101]);
+    halt();
+}
+
+fn f28() {
+    local1 = local1 * 2;
+    return;
+}
+"
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        let min = i64::MIN;
+        let program = [9, 0, 109, min, 204, min, 1001, 0, min, 0, 99];
+        assert_eq!(
+            decompile(&program).to_string(),
+            "fn main() {
+    rb += mem[0];
+    rb -= 9223372036854775808;
+    output(mem[rb-9223372036854775808]);
+    mem[0] = mem[0] - 9223372036854775808;
+    halt();
+}
+"
+        );
+        let program = [109, i64::MAX, 204, 1, 99];
+        assert_eq!(
+            decompile(&program).to_string(),
+            "fn main() {
+    output(mem[rb+1]);
+    halt();
+}
+"
+        );
+    }
+
+    #[test]
+    fn target_inside_an_instruction() {
+        // the jnz at 0 jumps to 6, inside the mul of its block
+        let program = [
+            1205, -2, 6, 2, 14, 4, 17, 4, 1106, 15, 1106, 10, 2, 0, 8, 2105, 1, -2,
+        ];
+        assert_eq!(
+            decompile(&program).to_string(),
+            "fn main() {
+    if (mem[-2] != 0) goto L6;
+    mem[17] = mem[14] * mem[4];
+    goto L7;
+    L6:
+    invalid(17);
+    L7:
+    output(mem[1106]);
+    invalid(15);
+}
+"
+        );
+        // the jnz at 4 of the comparison at 0 jumps to 6, inside itself
+        let program = [7, 9, 10, 11, 1005, 11, 6, 99, 99, 1, 2, 0];
+        assert_eq!(
+            decompile(&program).to_string(),
+            "fn main() {
+    if (mem[11] = mem[9] < mem[10]) goto L6;
+    goto L7;
+    L6:
+    if (mem[99] == 0) goto *mem[99];
+    goto L9;
+    L7:
+    halt();
+    L9:
+    mem[0] = mem[2] + mem[0];
+    invalid(0);
+}
+"
+        );
+    }
+}
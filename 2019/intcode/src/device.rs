@@ -0,0 +1,188 @@
+use alloc::boxed::Box;
+use core::ops::Range;
+#[cfg(feature = "std")]
+pub use shared::{Framebuffer, Keyboard};
+
+/// Something a range of the VM memory can be mapped to.
+///
+/// `offset` is relative to the start of the mapped range, below `len`.
+pub trait Device: Send {
+    /// The number of addresses of the device, the length of the range it is mapped to.
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn read(&mut self, offset: usize) -> i64;
+    fn write(&mut self, offset: usize, value: i64);
+    /// Called once after every instruction executed by the VM.
+    fn tick(&mut self) {}
+}
+
+pub(crate) struct Mapping {
+    pub(crate) range: Range<usize>,
+    pub(crate) device: Box<dyn Device>,
+}
+
+#[cfg(feature = "std")]
+mod shared {
+    use super::Device;
+    use std::collections::VecDeque;
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+    use std::vec;
+    use std::vec::Vec;
+
+    /// A `width` x `height` grid of cells, one memory address per cell.
+    ///
+    /// Clones share the same cells so the host can keep one to display what the program drew.
+    #[derive(Clone)]
+    pub struct Framebuffer {
+        width: usize,
+        height: usize,
+        cells: Arc<Mutex<Vec<i64>>>,
+    }
+
+    impl Framebuffer {
+        pub fn new(width: usize, height: usize) -> Self {
+            Self {
+                width,
+                height,
+                cells: Arc::new(Mutex::new(vec![0; width * height])),
+            }
+        }
+
+        pub fn get(&self, x: usize, y: usize) -> Option<i64> {
+            if x < self.width && y < self.height {
+                Some(self.cells.lock().unwrap()[y * self.width + x])
+            } else {
+                None
+            }
+        }
+    }
+
+    impl Device for Framebuffer {
+        fn len(&self) -> usize {
+            self.width * self.height
+        }
+
+        fn read(&mut self, offset: usize) -> i64 {
+            self.cells.lock().unwrap()[offset]
+        }
+
+        fn write(&mut self, offset: usize, value: i64) {
+            self.cells.lock().unwrap()[offset] = value;
+        }
+    }
+
+    impl fmt::Display for Framebuffer {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let cells = self.cells.lock().unwrap();
+            for row in cells.chunks(self.width.max(1)) {
+                for cell in row {
+                    let c = match cell {
+                        0 => ' ',
+                        1 => '#',
+                        32..=126 => *cell as u8 as char,
+                        _ => '?',
+                    };
+                    write!(f, "{}", c)?;
+                }
+                writeln!(f)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// A queue of keys pressed by the host.
+    ///
+    /// Reading offset 0 pops the next key, or 0 if none is pending, offset 1 gives the number of
+    /// pending keys. Clones share the same queue.
+    #[derive(Clone, Default)]
+    pub struct Keyboard {
+        keys: Arc<Mutex<VecDeque<i64>>>,
+    }
+
+    impl Keyboard {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn press(&self, key: i64) {
+            self.keys.lock().unwrap().push_back(key);
+        }
+    }
+
+    impl Device for Keyboard {
+        fn len(&self) -> usize {
+            2
+        }
+
+        fn read(&mut self, offset: usize) -> i64 {
+            let mut keys = self.keys.lock().unwrap();
+            match offset {
+                0 => keys.pop_front().unwrap_or(0),
+                _ => keys.len() as i64,
+            }
+        }
+
+        fn write(&mut self, _: usize, _: i64) {}
+    }
+}
+
+/// Counts the instructions executed since it was mapped, writing sets the count.
+#[derive(Default)]
+pub struct Timer {
+    ticks: i64,
+}
+
+impl Timer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Device for Timer {
+    fn len(&self) -> usize {
+        1
+    }
+
+    fn read(&mut self, _: usize) -> i64 {
+        self.ticks
+    }
+
+    fn write(&mut self, _: usize, value: i64) {
+        self.ticks = value;
+    }
+
+    fn tick(&mut self) {
+        self.ticks = self.ticks.wrapping_add(1);
+    }
+}
+
+/// A xorshift generator, every read gives a new non negative number, writing reseeds it.
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed.max(1) }
+    }
+}
+
+impl Device for Random {
+    fn len(&self) -> usize {
+        1
+    }
+
+    fn read(&mut self, _: usize) -> i64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        (self.state >> 1) as i64
+    }
+
+    fn write(&mut self, _: usize, value: i64) {
+        *self = Self::new(value as u64);
+    }
+}
@@ -0,0 +1,154 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::VM;
+
+/// A copy of the VM memory and registers at one point in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub mem: Vec<i64>,
+    pub p: usize,
+    pub r: i64,
+    pub last_write: Option<usize>,
+}
+
+/// A memory cell that differs between two snapshots.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Change {
+    pub address: usize,
+    pub before: i64,
+    pub after: i64,
+}
+
+impl VM {
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            mem: self.mem.clone(),
+            p: self.p,
+            r: self.r,
+            last_write: self.last_write(),
+        }
+    }
+}
+
+impl Snapshot {
+    /// Cells changed from `self` to `other`, memory beyond the end of a snapshot is zero.
+    pub fn diff(&self, other: &Snapshot) -> Vec<Change> {
+        let len = self.mem.len().max(other.mem.len());
+        (0..len)
+            .filter_map(|address| {
+                let before = self.mem.get(address).copied().unwrap_or(0);
+                let after = other.mem.get(address).copied().unwrap_or(0);
+                if before != after {
+                    Some(Change {
+                        address,
+                        before,
+                        after,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn annotations(&self, address: usize) -> String {
+        let mut annotations = Vec::new();
+        if address == self.p {
+            annotations.push("<- p");
+        }
+        if self.r >= 0 && address == self.r as usize {
+            annotations.push("<- r");
+        }
+        if Some(address) == self.last_write {
+            annotations.push("<- last write");
+        }
+        annotations.join(" ")
+    }
+}
+
+/// One cell per line with its address, hex and decimal value, runs of zero are folded into `*`.
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:>6}  {:>16}  {:>20}", "addr", "hex", "decimal")?;
+        let mut folded = false;
+        for (address, value) in self.mem.iter().enumerate() {
+            let annotations = self.annotations(address);
+            let previous = address.checked_sub(1).map(|i| self.mem[i]);
+            if *value == 0 && previous == Some(0) && annotations.is_empty() {
+                if !folded {
+                    writeln!(f, "*")?;
+                    folded = true;
+                }
+                continue;
+            }
+            folded = false;
+            write!(f, "{:06}  {:016x}  {:>20}", address, value, value)?;
+            if annotations.is_empty() {
+                writeln!(f)?;
+            } else {
+                writeln!(f, "  {}", annotations)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:06}: {} -> {}", self.address, self.before, self.after)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use std::string::ToString;
+    use std::vec;
+
+    #[test]
+    fn diff() -> Result<(), VMError> {
+        let mut vm = VM::new(vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50], None);
+        let before = vm.snapshot();
+        assert_eq!(vm.run()?, State::Halt);
+        let after = vm.snapshot();
+        assert_eq!(after.last_write, Some(0));
+        assert_eq!(
+            before.diff(&after),
+            vec![
+                Change {
+                    address: 0,
+                    before: 1,
+                    after: 3500
+                },
+                Change {
+                    address: 3,
+                    before: 3,
+                    after: 70
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn display() {
+        let snapshot = Snapshot {
+            mem: vec![-1, 0, 0, 0, 5],
+            p: 4,
+            r: 0,
+            last_write: Some(1),
+        };
+        assert_eq!(
+            snapshot.to_string(),
+            "  addr               hex               decimal
+000000  ffffffffffffffff                    -1  <- r
+000001  0000000000000000                     0  <- last write
+*
+000004  0000000000000005                     5  <- p
+"
+        );
+    }
+}
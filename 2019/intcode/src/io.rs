@@ -0,0 +1,135 @@
+use std::boxed::Box;
+use std::eprintln;
+use std::error::Error;
+use std::io::{BufRead, Write};
+use std::string::String;
+
+use crate::{State, VMError, VM};
+
+/// How values are exchanged with the host.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Format {
+    /// Numbers separated by commas or whitespaces in, one number per line out.
+    #[default]
+    Numbers,
+    /// Every byte of a line is an input, including the newline. Outputs are characters, values
+    /// out of the ASCII range are written as a number on their own line.
+    Ascii,
+}
+
+/// Connect a `VM` to a reader and a writer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Io {
+    pub input: Format,
+    pub output: Format,
+    /// Print every instruction to stderr before executing it.
+    pub trace: bool,
+}
+
+impl Io {
+    /// Run `vm` until it halts, reading a line from `input` each time it needs an input.
+    pub fn run<R, W>(&self, vm: &mut VM, mut input: R, mut output: W) -> Result<(), Box<dyn Error>>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        let mut line = String::new();
+        loop {
+            let state = if self.trace {
+                eprintln!("{}", vm.decode()?);
+                vm.tick()?
+            } else {
+                vm.run()?
+            };
+            match state {
+                State::Continue => {}
+                State::Output(o) => self.write(&mut output, o)?,
+                State::Input => {
+                    line.clear();
+                    if input.read_line(&mut line)? == 0 {
+                        return Err(Box::new(VMError::Empty));
+                    }
+                    self.read(vm, &line)?;
+                }
+                State::Halt => break Ok(()),
+            }
+        }
+    }
+
+    fn read(&self, vm: &mut VM, line: &str) -> Result<(), Box<dyn Error>> {
+        match self.input {
+            Format::Numbers => {
+                for token in line
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|token| !token.is_empty())
+                {
+                    vm.inputs.push_back(token.parse()?);
+                }
+            }
+            Format::Ascii => vm.inputs.extend(line.bytes().map(i64::from)),
+        }
+        Ok(())
+    }
+
+    fn write<W>(&self, output: &mut W, value: i64) -> Result<(), Box<dyn Error>>
+    where
+        W: Write,
+    {
+        match self.output {
+            Format::Ascii if (0..128).contains(&value) => {
+                write!(output, "{}", value as u8 as char)?
+            }
+            Format::Ascii => writeln!(output, "\n{}", value)?,
+            Format::Numbers => writeln!(output, "{}", value)?,
+        }
+        Ok(())
+    }
+}
+
+/// Run `vm` until it halts, exchanging numbers with `input` and `output`.
+pub fn run<R, W>(vm: &mut VM, input: R, output: W) -> Result<(), Box<dyn Error>>
+where
+    R: BufRead,
+    W: Write,
+{
+    Io::default().run(vm, input, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions;
+    use std::vec::Vec;
+
+    #[test]
+    fn echo_sum() -> Result<(), Box<dyn Error>> {
+        // output the sum of two inputs
+        let program = instructions("3,11,3,12,1,11,12,13,4,13,99,0,0,0")
+            .unwrap()
+            .1;
+        let mut output = Vec::new();
+        run(
+            &mut VM::new(program, None),
+            "40\n2\n".as_bytes(),
+            &mut output,
+        )?;
+        assert_eq!(output, b"42\n");
+        Ok(())
+    }
+
+    #[test]
+    fn ascii() -> Result<(), Box<dyn Error>> {
+        let program = instructions("3,11,3,12,1,11,12,13,4,13,99,0,0,0")
+            .unwrap()
+            .1;
+        let io = Io {
+            input: Format::Ascii,
+            output: Format::Ascii,
+            trace: false,
+        };
+        let mut output = Vec::new();
+        io.run(&mut VM::new(program, None), "&\n".as_bytes(), &mut output)?;
+        assert_eq!(output, b"0");
+        Ok(())
+    }
+}
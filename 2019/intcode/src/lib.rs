@@ -0,0 +1,594 @@
+//! An Intcode virtual machine.
+//!
+//! The core only needs `alloc`, the `std` feature (on by default) adds the devices sharing
+//! state with the host and the I/O adapters of the `io` module.
+#![no_std]
+
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::Range;
+
+pub mod coverage;
+pub mod decompile;
+pub mod device;
+pub mod dump;
+#[cfg(feature = "std")]
+pub mod io;
+mod opcode;
+#[cfg(feature = "std")]
+mod parse;
+#[cfg(feature = "std")]
+pub mod scheduler;
+pub mod search;
+pub mod selfmod;
+pub mod taint;
+
+use coverage::Coverage;
+use device::{Device, Mapping};
+pub use opcode::{Args, Handler, Instruction, Opcode, Opcodes, Operand, Param, MAX_ARITY};
+#[cfg(feature = "std")]
+pub use parse::instructions;
+use selfmod::SelfModification;
+use taint::Taint;
+
+trait DivPlus: Sized {
+    fn div(self, other: Self) -> (Self, Self);
+}
+
+impl DivPlus for i64 {
+    fn div(self, other: Self) -> (Self, Self) {
+        (self / other, self % other)
+    }
+}
+
+pub struct VM {
+    pub mem: Vec<i64>,
+    pub inputs: VecDeque<i64>,
+    pub p: usize,
+    pub r: i64,
+    /// Number of instructions executed so far.
+    pub cycles: u64,
+    opcodes: Opcodes,
+    devices: Vec<Mapping>,
+    coverage: Option<Coverage>,
+    taint: Option<Taint>,
+    selfmod: Option<SelfModification>,
+    last_write: Option<usize>,
+    max_mem: Option<usize>,
+}
+
+#[derive(Debug)]
+pub enum VMError {
+    Output,
+    Mode,
+    Opcode(i64),
+    Empty,
+    /// A negative address or jump target.
+    Address(i64),
+    /// Raised by custom opcodes.
+    Trap(i64),
+    /// A write beyond the limit given to `VM::limit_memory`.
+    Memory(usize),
+    /// A device can't be mapped there: the range is empty, isn't as long as the device,
+    /// overlaps the program or another device, or the VM already started.
+    Map(Range<usize>),
+    /// A write into the code with `VM::watch_code` in strict mode, gives the address written.
+    SelfModification(usize),
+    /// An `add`, a `mul` or the relative base went out of the `i64` range.
+    Overflow,
+}
+
+impl fmt::Display for VMError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VMError: {:?}", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VMError {}
+
+#[derive(Debug, PartialEq)]
+pub enum State {
+    Continue,
+    Output(i64),
+    /// The VM needs an input, `p` stays on the input instruction so `run` can be called again
+    /// once one is pushed.
+    Input,
+    Halt,
+}
+
+impl State {
+    pub fn output(self) -> Result<i64, VMError> {
+        if let State::Output(o) = self {
+            Ok(o)
+        } else {
+            Err(VMError::Output)
+        }
+    }
+}
+
+impl VM {
+    pub fn new<I>(mem: Vec<i64>, inputs: I) -> Self
+    where
+        I: IntoIterator<Item = i64>,
+    {
+        Self::with_opcodes(mem, inputs, Opcodes::default())
+    }
+
+    pub fn with_opcodes<I>(mem: Vec<i64>, inputs: I, opcodes: Opcodes) -> Self
+    where
+        I: IntoIterator<Item = i64>,
+    {
+        Self {
+            mem,
+            inputs: inputs.into_iter().collect(),
+            p: 0,
+            r: 0,
+            cycles: 0,
+            opcodes,
+            devices: Vec::new(),
+            coverage: None,
+            taint: None,
+            selfmod: None,
+            last_write: None,
+            max_mem: None,
+        }
+    }
+
+    /// Fail instead of growing the memory beyond `cells` cells.
+    pub fn limit_memory(&mut self, cells: usize) {
+        self.max_mem = Some(cells);
+    }
+
+    /// Start recording which addresses are executed, read and written.
+    pub fn enable_coverage(&mut self) {
+        self.coverage = Some(Coverage::new(self.mem.len()));
+    }
+
+    pub fn coverage(&self) -> Option<&Coverage> {
+        self.coverage.as_ref()
+    }
+
+    /// Start following which inputs every value depends on, see `Taint`.
+    pub fn enable_taint(&mut self) {
+        self.taint = Some(Taint::new(self.mem.len()));
+    }
+
+    pub fn taint(&self) -> Option<&Taint> {
+        self.taint.as_ref()
+    }
+
+    /// Start recording the writes into the code, with `strict` they fail with
+    /// `VMError::SelfModification` before anything is written.
+    pub fn watch_code(&mut self, strict: bool) {
+        self.selfmod = Some(SelfModification::new(self.mem.len(), strict));
+    }
+
+    pub fn self_modification(&self) -> Option<&SelfModification> {
+        self.selfmod.as_ref()
+    }
+
+    /// Address of the last cell written by `put`.
+    pub fn last_write(&self) -> Option<usize> {
+        self.last_write
+    }
+
+    /// Route the reads and writes of `range` to `device` instead of `mem`.
+    ///
+    /// Devices must be mapped before the first instruction is executed, outside of the program.
+    pub fn map<D>(&mut self, range: Range<usize>, device: D) -> Result<(), VMError>
+    where
+        D: Device + 'static,
+    {
+        let overlap = |other: &Range<usize>| range.start < other.end && other.start < range.end;
+        if self.cycles != 0
+            || range.start >= range.end
+            || range.len() != device.len()
+            || overlap(&(0..self.mem.len()))
+            || self.devices.iter().any(|mapping| overlap(&mapping.range))
+        {
+            Err(VMError::Map(range))
+        } else {
+            self.devices.push(Mapping {
+                range,
+                device: Box::new(device),
+            });
+            Ok(())
+        }
+    }
+
+    pub fn opcodes(&self) -> &Opcodes {
+        &self.opcodes
+    }
+
+    pub fn run(&mut self) -> Result<State, VMError> {
+        loop {
+            match self.tick()? {
+                State::Continue => continue,
+                state => break Ok(state),
+            }
+        }
+    }
+
+    pub fn tick(&mut self) -> Result<State, VMError> {
+        let instruction = self.decode()?;
+        let handler = self
+            .opcodes
+            .get(instruction.opcode)
+            .ok_or(VMError::Opcode(instruction.opcode))?
+            .handler()
+            .clone();
+        if let Some(taint) = &mut self.taint {
+            taint.begin();
+        }
+        let writes = self.selfmod.as_ref().map(|selfmod| selfmod.writes().len());
+        let args = match self.resolve(&instruction) {
+            Ok(args) => args,
+            Err(e) => {
+                self.unwrite(writes);
+                return Err(e);
+            }
+        };
+        self.p = instruction.next();
+        let inputs = self.inputs.len();
+        let state = (handler)(self, &args);
+        if let Ok(State::Input) | Err(_) = state {
+            // stay on the instruction waiting for an input or failing, nothing was written, it
+            // will be checked again
+            self.p = instruction.address;
+            self.unwrite(writes);
+            return state;
+        }
+        // only now, an instruction waiting for an input or failing isn't executed
+        if let Some(coverage) = &mut self.coverage {
+            coverage.execute(instruction.address..instruction.next());
+        }
+        if let Some(selfmod) = &mut self.selfmod {
+            selfmod.execute(instruction.address..instruction.next());
+        }
+        if let Some(taint) = &mut self.taint {
+            taint.consume(inputs.saturating_sub(self.inputs.len()));
+            if let Ok(State::Output(_)) = state {
+                taint.output();
+            }
+            // the conditional jumps of the 2019 instruction set
+            if let 5 | 6 = instruction.opcode {
+                taint.branch(instruction.address, self.p != instruction.next());
+            }
+        }
+        self.cycles += 1;
+        for mapping in self.devices.iter_mut() {
+            mapping.device.tick();
+        }
+        state
+    }
+
+    /// Decode the instruction at `p` without executing it.
+    pub fn decode(&self) -> Result<Instruction, VMError> {
+        self.opcodes.decode(&self.mem, self.p)
+    }
+
+    /// Forget the writes into the code recorded since there were `writes`.
+    fn unwrite(&mut self, writes: Option<usize>) {
+        if let (Some(selfmod), Some(writes)) = (&mut self.selfmod, writes) {
+            selfmod.truncate(writes);
+        }
+    }
+
+    fn resolve(&mut self, instruction: &Instruction) -> Result<Args, VMError> {
+        let mut args = Args::default();
+        for (i, (param, operand)) in instruction
+            .params
+            .iter()
+            .zip(instruction.operands.iter())
+            .enumerate()
+        {
+            if let (Some(taint), Param::Read) = (&mut self.taint, param) {
+                // the operand itself may have been rewritten from an input
+                taint.read(instruction.address + 1 + i);
+            }
+            let address = match operand {
+                Operand::Position(index) => *index,
+                Operand::Relative(offset) => {
+                    self.r.checked_add(*offset).ok_or(VMError::Overflow)?
+                }
+                Operand::Immediate(value) => {
+                    args.values[i] = *value;
+                    continue;
+                }
+            };
+            let address = usize::try_from(address).map_err(|_| VMError::Address(address))?;
+            args.values[i] = match param {
+                Param::Read => {
+                    if let Some(coverage) = &mut self.coverage {
+                        coverage.read(address);
+                    }
+                    if let Some(taint) = &mut self.taint {
+                        taint.read(address);
+                    }
+                    self.load(address)
+                }
+                Param::Write => {
+                    if self.max_mem.is_some_and(|max| address >= max) {
+                        return Err(VMError::Memory(address));
+                    }
+                    if let Some(selfmod) = &mut self.selfmod {
+                        let next = match self.opcodes.decode(&self.mem, instruction.next()) {
+                            Ok(next) => next.address..next.next(),
+                            Err(_) => instruction.next()..instruction.next() + 1,
+                        };
+                        let current = instruction.address..instruction.next();
+                        if let Some(write) = selfmod.write(current, address, next) {
+                            if selfmod.is_strict() {
+                                return Err(VMError::SelfModification(write.address));
+                            }
+                        }
+                    }
+                    address as i64
+                }
+            };
+        }
+        Ok(args)
+    }
+
+    /// Instructions are always fetched from `mem`, never from a device.
+    fn fetch(&self, index: usize) -> i64 {
+        self.mem.get(index).copied().unwrap_or(0)
+    }
+
+    fn device(&mut self, index: usize) -> Option<(&mut Box<dyn Device>, usize)> {
+        self.devices
+            .iter_mut()
+            .find(|mapping| mapping.range.contains(&index))
+            .map(|mapping| (&mut mapping.device, index - mapping.range.start))
+    }
+
+    /// Read the memory cell at `index`, memory beyond the program is zero.
+    pub fn load(&mut self, index: usize) -> i64 {
+        match self.device(index) {
+            Some((device, offset)) => device.read(offset),
+            None => self.fetch(index),
+        }
+    }
+
+    /// Write `value` at `index`, growing the memory if needed, and return the old value.
+    ///
+    /// Writes to a device always return 0.
+    pub fn put(&mut self, index: usize, value: i64) -> i64 {
+        if let Some(coverage) = &mut self.coverage {
+            coverage.write(index);
+        }
+        if let Some(taint) = &mut self.taint {
+            taint.write(index);
+        }
+        self.last_write = Some(index);
+        if let Some((device, offset)) = self.device(index) {
+            device.write(offset, value);
+            return 0;
+        }
+        if self.mem.len() < index + 1 {
+            self.mem.resize(index + 1, 0);
+        }
+        core::mem::replace(&mut self.mem[index], value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::string::ToString;
+    use std::sync::{Arc, Mutex};
+    use std::vec;
+    use std::vec::Vec;
+
+    /// Without `instructions`, the tests run without `std` too.
+    fn program(input: &str) -> Vec<i64> {
+        input
+            .split(',')
+            .map(|v| v.trim().parse().unwrap())
+            .collect()
+    }
+
+    fn outputs(vm: &mut VM) -> Result<Vec<i64>, VMError> {
+        let mut outputs = Vec::new();
+        loop {
+            match vm.run()? {
+                State::Output(o) => outputs.push(o),
+                _ => break Ok(outputs),
+            }
+        }
+    }
+
+    #[test]
+    fn quine() -> Result<(), VMError> {
+        let quine = program("109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99");
+        let mut vm = VM::new(quine.clone(), None);
+        assert_eq!(outputs(&mut vm)?, quine);
+        Ok(())
+    }
+
+    #[test]
+    fn compare_input() -> Result<(), VMError> {
+        let equal_to_eight = program("3,9,8,9,10,9,4,9,99,-1,8");
+        assert_eq!(
+            VM::new(equal_to_eight.clone(), Some(8)).run()?,
+            State::Output(1)
+        );
+        assert_eq!(VM::new(equal_to_eight, Some(7)).run()?, State::Output(0));
+        Ok(())
+    }
+
+    #[test]
+    fn debug_print() -> Result<(), VMError> {
+        let printed = Arc::new(Mutex::new(Vec::new()));
+        let log = printed.clone();
+        let mut opcodes = Opcodes::default();
+        opcodes.insert(
+            42,
+            Opcode::new("debug", &[Param::Read, Param::Read], move |_, args| {
+                log.lock().unwrap().push((args.value(0), args.value(1)));
+                Ok(State::Continue)
+            }),
+        );
+        let mut vm = VM::with_opcodes(program("1142,7,8,1042,0,1,99"), None, opcodes);
+        assert_eq!(vm.run()?, State::Halt);
+        assert_eq!(*printed.lock().unwrap(), vec![(7, 8), (1142, 1)]);
+        Ok(())
+    }
+
+    #[test]
+    fn trap() {
+        let mut opcodes = Opcodes::default();
+        opcodes.insert(
+            13,
+            Opcode::new("trap", &[Param::Read], |_, args| {
+                Err(VMError::Trap(args.value(0)))
+            }),
+        );
+        let mut vm = VM::with_opcodes(program("1101,2,3,0,113,77,99"), None, opcodes);
+        match vm.run() {
+            Err(VMError::Trap(77)) => {}
+            Err(e) => panic!("unexpected error {:?}", e),
+            Ok(state) => panic!("trap didn't fire: {:?}", state),
+        }
+        assert_eq!(vm.mem[0], 5);
+    }
+
+    #[test]
+    fn unknown_opcode() {
+        let mut opcodes = Opcodes::default();
+        assert!(opcodes.remove(2).is_some());
+        let mut vm = VM::with_opcodes(program("2,0,0,0,99"), None, opcodes);
+        assert!(vm.run().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn devices() -> Result<(), VMError> {
+        use device::{Framebuffer, Keyboard, Timer};
+
+        // draw the keys pressed on the screen until no key is pending
+        let mut vm = VM::new(
+            program("109,100,1001,200,0,19,1006,19,18,21001,19,0,0,109,1,1105,1,2,99,0"),
+            None,
+        );
+        let screen = Framebuffer::new(2, 2);
+        let keyboard = Keyboard::new();
+        vm.map(100..104, screen.clone())?;
+        vm.map(200..202, keyboard.clone())?;
+        assert!(vm.map(10..30, Timer::new()).is_err());
+        assert!(vm.map(400..500, Framebuffer::new(2, 2)).is_err());
+        assert!(vm.map(103..104, Timer::new()).is_err());
+        keyboard.press(72);
+        keyboard.press(105);
+        assert_eq!(vm.run()?, State::Halt);
+        assert_eq!(screen.to_string(), "Hi\n  \n");
+        assert_eq!(vm.mem.len(), 20);
+        assert!(vm.map(300..301, Timer::new()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn coverage() -> Result<(), VMError> {
+        let equal_to_eight = program("3,9,8,9,10,9,4,9,99,-1,8");
+        let mut vm = VM::new(equal_to_eight, Some(8));
+        vm.enable_coverage();
+        assert_eq!(vm.run()?, State::Output(1));
+        let coverage = vm.coverage().unwrap();
+        assert_eq!(
+            coverage.executed().collect::<Vec<_>>(),
+            (0..8).collect::<Vec<_>>()
+        );
+        assert!(coverage.is_written(9) && coverage.is_read(9) && coverage.is_read(10));
+        assert_eq!(coverage.to_string(), "00000 XXXXXXXX.br\n");
+
+        // waiting for an input or failing doesn't execute the instruction
+        let mut vm = VM::new(program("3,9,8,9,10,9,4,9,99,-1,8"), None);
+        vm.enable_coverage();
+        assert_eq!(vm.run()?, State::Input);
+        assert_eq!(vm.coverage().unwrap().executed().count(), 0);
+        let mut vm = VM::new(program("1105,1,-1"), None);
+        vm.enable_coverage();
+        assert!(vm.run().is_err());
+        assert_eq!(vm.coverage().unwrap().executed().count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn taint() -> Result<(), VMError> {
+        // day7 amplifier: phase + 10 * signal
+        let amplifier = program("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0");
+        let mut vm = VM::new(amplifier, vec![4, 0]);
+        vm.enable_taint();
+        assert_eq!(vm.run()?, State::Output(4));
+        let taint = vm.taint().unwrap();
+        assert_eq!(taint.consumed(), 2);
+        assert_eq!(taint.outputs()[0].to_string(), "{0, 1}");
+        assert!(taint.branches().is_empty());
+
+        // jump if the input is zero
+        let is_zero = program("3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9");
+        let mut vm = VM::new(is_zero, Some(0));
+        vm.enable_taint();
+        assert_eq!(vm.run()?, State::Output(0));
+        let taint = vm.taint().unwrap();
+        assert_eq!(
+            taint.branches(),
+            [taint::Branch {
+                address: 2,
+                taken: true,
+                labels: taint::Labels::input(0),
+            }]
+        );
+        // the output is a constant chosen by the branch
+        assert!(taint.outputs()[0].is_empty());
+        assert!(taint.cell(12).contains(0));
+        Ok(())
+    }
+
+    #[test]
+    fn self_modification() -> Result<(), VMError> {
+        // day5 adds its input to the opcode of the next instruction
+        let rewrite = program("3,20,1,20,6,6,100,42,99");
+        let mut vm = VM::new(rewrite.clone(), Some(4));
+        vm.watch_code(false);
+        assert_eq!(vm.run()?, State::Output(42));
+        let writes = vm.self_modification().unwrap().writes();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(
+            writes[0].to_string(),
+            "00002: wrote 00006, the next instruction"
+        );
+
+        let mut vm = VM::new(rewrite, Some(4));
+        vm.watch_code(true);
+        assert!(matches!(vm.run(), Err(VMError::SelfModification(6))));
+        assert_eq!((vm.p, vm.mem[6]), (2, 100));
+        // the write was refused, not made
+        assert!(vm.self_modification().unwrap().writes().is_empty());
+
+        // the add at 0 overflows, its cells aren't executed when the one at 4 writes them
+        let mut vm = VM::new(vec![1101, i64::MAX, 1, 20, 1101, 1, 1, 1, 99], None);
+        vm.watch_code(false);
+        assert!(matches!(vm.run(), Err(VMError::Overflow)));
+        vm.p = 4;
+        assert_eq!(vm.run()?, State::Halt);
+        assert!(vm.self_modification().unwrap().writes().is_empty());
+
+        // writing data only, the input waits without being recorded twice
+        let mut vm = VM::new(program("3,9,8,9,10,9,4,9,99,-1,8"), None);
+        vm.watch_code(true);
+        assert_eq!(vm.run()?, State::Input);
+        vm.inputs.push_back(8);
+        assert_eq!(vm.run()?, State::Output(1));
+        assert!(vm.self_modification().unwrap().writes().is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,278 @@
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+use crate::{DivPlus, State, VMError, VM};
+
+/// Maximum number of parameters an opcode can declare.
+pub const MAX_ARITY: usize = 3;
+
+/// How an opcode uses one of its parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Param {
+    /// The parameter is resolved to a value before the handler runs.
+    Read,
+    /// The parameter is resolved to the address the handler writes to.
+    Write,
+}
+
+/// A parameter as it is encoded in memory, before being resolved.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operand {
+    Position(i64),
+    Immediate(i64),
+    Relative(i64),
+}
+
+/// A decoded instruction.
+#[derive(Debug, Clone, Copy)]
+pub struct Instruction {
+    pub address: usize,
+    pub opcode: i64,
+    pub name: &'static str,
+    pub params: &'static [Param],
+    pub operands: [Operand; MAX_ARITY],
+}
+
+impl Instruction {
+    pub fn operands(&self) -> &[Operand] {
+        &self.operands[..self.params.len()]
+    }
+
+    /// Address of the instruction that follows this one.
+    pub fn next(&self) -> usize {
+        self.address + 1 + self.params.len()
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Position(address) => write!(f, "[{}]", address),
+            Operand::Immediate(value) => write!(f, "{}", value),
+            Operand::Relative(offset) if *offset < 0 => write!(f, "[rb-{}]", offset.unsigned_abs()),
+            Operand::Relative(offset) => write!(f, "[rb+{}]", offset),
+        }
+    }
+}
+
+/// `address: name operand, ...`, positions are written `[address]` and relatives `[rb+offset]`.
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:05}: {}", self.address, self.name)?;
+        for (i, operand) in self.operands().iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { " " } else { ", " }, operand)?;
+        }
+        Ok(())
+    }
+}
+
+/// Resolved parameters given to a handler: values for `Param::Read`, addresses for `Param::Write`.
+#[derive(Debug, Default)]
+pub struct Args {
+    pub(crate) values: [i64; MAX_ARITY],
+}
+
+impl Args {
+    pub fn value(&self, i: usize) -> i64 {
+        self.values[i]
+    }
+
+    pub fn address(&self, i: usize) -> usize {
+        self.values[i] as usize
+    }
+}
+
+pub type Handler = Arc<dyn Fn(&mut VM, &Args) -> Result<State, VMError> + Send + Sync>;
+
+#[derive(Clone)]
+pub struct Opcode {
+    pub name: &'static str,
+    pub params: &'static [Param],
+    handler: Handler,
+}
+
+impl Opcode {
+    /// # Panics
+    ///
+    /// Panics if more than `MAX_ARITY` parameters are declared.
+    pub fn new<F>(name: &'static str, params: &'static [Param], handler: F) -> Self
+    where
+        F: Fn(&mut VM, &Args) -> Result<State, VMError> + Send + Sync + 'static,
+    {
+        assert!(
+            params.len() <= MAX_ARITY,
+            "too many parameters for {}",
+            name
+        );
+        Self {
+            name,
+            params,
+            handler: Arc::new(handler),
+        }
+    }
+
+    pub fn arity(&self) -> usize {
+        self.params.len()
+    }
+
+    pub fn handler(&self) -> &Handler {
+        &self.handler
+    }
+}
+
+/// The opcodes known by a `VM`, indexed by the two lowest digits of an instruction.
+#[derive(Clone)]
+pub struct Opcodes {
+    opcodes: Vec<Option<Opcode>>,
+}
+
+use Param::{Read, Write};
+
+impl Opcodes {
+    pub fn empty() -> Self {
+        Self {
+            opcodes: vec![None; 100],
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `code` doesn't fit in two digits.
+    pub fn insert(&mut self, code: usize, opcode: Opcode) -> Option<Opcode> {
+        assert!(code < 100, "opcode {} doesn't fit in two digits", code);
+        self.opcodes[code].replace(opcode)
+    }
+
+    pub fn remove(&mut self, code: usize) -> Option<Opcode> {
+        self.opcodes.get_mut(code).and_then(Option::take)
+    }
+
+    pub fn get(&self, code: i64) -> Option<&Opcode> {
+        if code < 0 {
+            None
+        } else {
+            self.opcodes.get(code as usize).and_then(Option::as_ref)
+        }
+    }
+
+    /// Decode the instruction at `address` of `mem`, cells beyond `mem` read as 0.
+    pub fn decode(&self, mem: &[i64], address: usize) -> Result<Instruction, VMError> {
+        let fetch = |index: usize| mem.get(index).copied().unwrap_or(0);
+        let (r, op) = fetch(address).div(100);
+        let opcode = self.get(op).ok_or(VMError::Opcode(op))?;
+        let mut instruction = Instruction {
+            address,
+            opcode: op,
+            name: opcode.name,
+            params: opcode.params,
+            operands: [Operand::Immediate(0); MAX_ARITY],
+        };
+        let mut r = r;
+        for (i, param) in opcode.params.iter().enumerate() {
+            let (next, mode) = r.div(10);
+            r = next;
+            let value = fetch(address + 1 + i);
+            instruction.operands[i] = match (mode, param) {
+                (0, _) => Operand::Position(value),
+                (1, Param::Read) => Operand::Immediate(value),
+                (2, _) => Operand::Relative(value),
+                _ => return Err(VMError::Mode),
+            };
+        }
+        if r != 0 {
+            Err(VMError::Opcode(r))
+        } else {
+            Ok(instruction)
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &Opcode)> {
+        self.opcodes
+            .iter()
+            .enumerate()
+            .filter_map(|(code, opcode)| opcode.as_ref().map(|opcode| (code, opcode)))
+    }
+}
+
+impl Default for Opcodes {
+    /// The instruction set of the 2019 puzzles.
+    fn default() -> Self {
+        let mut opcodes = Self::empty();
+        opcodes.insert(
+            1,
+            Opcode::new("add", &[Read, Read, Write], |vm, args| {
+                let sum = args.value(0).checked_add(args.value(1));
+                vm.put(args.address(2), sum.ok_or(VMError::Overflow)?);
+                Ok(State::Continue)
+            }),
+        );
+        opcodes.insert(
+            2,
+            Opcode::new("mul", &[Read, Read, Write], |vm, args| {
+                let product = args.value(0).checked_mul(args.value(1));
+                vm.put(args.address(2), product.ok_or(VMError::Overflow)?);
+                Ok(State::Continue)
+            }),
+        );
+        opcodes.insert(
+            3,
+            Opcode::new("in", &[Write], |vm, args| match vm.inputs.pop_front() {
+                Some(value) => {
+                    vm.put(args.address(0), value);
+                    Ok(State::Continue)
+                }
+                None => Ok(State::Input),
+            }),
+        );
+        opcodes.insert(
+            4,
+            Opcode::new("out", &[Read], |_, args| Ok(State::Output(args.value(0)))),
+        );
+        opcodes.insert(
+            5,
+            Opcode::new("jnz", &[Read, Read], |vm, args| {
+                if args.value(0) != 0 {
+                    vm.p = usize::try_from(args.value(1))
+                        .map_err(|_| VMError::Address(args.value(1)))?;
+                }
+                Ok(State::Continue)
+            }),
+        );
+        opcodes.insert(
+            6,
+            Opcode::new("jz", &[Read, Read], |vm, args| {
+                if args.value(0) == 0 {
+                    vm.p = usize::try_from(args.value(1))
+                        .map_err(|_| VMError::Address(args.value(1)))?;
+                }
+                Ok(State::Continue)
+            }),
+        );
+        opcodes.insert(
+            7,
+            Opcode::new("lt", &[Read, Read, Write], |vm, args| {
+                vm.put(args.address(2), (args.value(0) < args.value(1)) as i64);
+                Ok(State::Continue)
+            }),
+        );
+        opcodes.insert(
+            8,
+            Opcode::new("eq", &[Read, Read, Write], |vm, args| {
+                vm.put(args.address(2), (args.value(0) == args.value(1)) as i64);
+                Ok(State::Continue)
+            }),
+        );
+        opcodes.insert(
+            9,
+            Opcode::new("arb", &[Read], |vm, args| {
+                vm.r = vm.r.checked_add(args.value(0)).ok_or(VMError::Overflow)?;
+                Ok(State::Continue)
+            }),
+        );
+        opcodes.insert(99, Opcode::new("halt", &[], |_, _| Ok(State::Halt)));
+        opcodes
+    }
+}
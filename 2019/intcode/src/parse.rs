@@ -0,0 +1,24 @@
+use nom::{
+    character::complete::{char, digit1},
+    combinator::all_consuming,
+    combinator::cut,
+    combinator::map_res,
+    combinator::opt,
+    combinator::recognize,
+    multi::separated_nonempty_list,
+    IResult,
+};
+
+use std::str::FromStr;
+use std::vec::Vec;
+
+fn parse_i64(input: &str) -> IResult<&str, i64> {
+    let (input, neg) = opt(char('-'))(input)?;
+    let (input, n) = map_res(recognize(digit1), i64::from_str)(input)?;
+    Ok((input, if neg.is_some() { -n } else { n }))
+}
+
+/// The values of a program separated by commas, a value missing fails where it was expected.
+pub fn instructions(input: &str) -> IResult<&str, Vec<i64>> {
+    all_consuming(separated_nonempty_list(char(','), cut(parse_i64)))(input)
+}
@@ -0,0 +1,296 @@
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use std::vec::Vec;
+
+use crate::{State, VMError, VM};
+
+/// Instructions a machine runs before giving its worker to the next ready machine.
+const QUANTUM: u64 = 10_000;
+
+/// A machine spawned on a `Scheduler`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Machine(usize);
+
+struct Slot {
+    /// `None` while a worker runs the machine.
+    vm: Option<VM>,
+    inbox: VecDeque<i64>,
+    outputs: Vec<i64>,
+    /// Waiting for an input, not in the ready queue.
+    parked: bool,
+    halted: bool,
+}
+
+#[derive(Default)]
+struct Queue {
+    ready: VecDeque<usize>,
+    running: usize,
+    error: Option<(Machine, VMError)>,
+}
+
+/// Runs many VMs on a fixed number of worker threads, the outputs of a machine are sent to
+/// the inputs of the machines it is connected to.
+///
+/// A machine needing an input is parked until one arrives. With a single worker everything
+/// runs on the calling thread in a fixed order, so the results are reproducible.
+pub struct Scheduler {
+    workers: usize,
+    slots: Vec<Mutex<Slot>>,
+    links: Vec<Vec<usize>>,
+    queue: Mutex<Queue>,
+    wake: Condvar,
+}
+
+impl Scheduler {
+    /// # Panics
+    ///
+    /// Panics if `workers` is 0.
+    pub fn new(workers: usize) -> Self {
+        assert!(workers > 0, "a scheduler needs at least one worker");
+        Self {
+            workers,
+            slots: Vec::new(),
+            links: Vec::new(),
+            queue: Mutex::new(Queue::default()),
+            wake: Condvar::new(),
+        }
+    }
+
+    pub fn spawn(&mut self, vm: VM) -> Machine {
+        self.slots.push(Mutex::new(Slot {
+            vm: Some(vm),
+            inbox: VecDeque::new(),
+            outputs: Vec::new(),
+            parked: false,
+            halted: false,
+        }));
+        self.links.push(Vec::new());
+        self.queue
+            .get_mut()
+            .unwrap()
+            .ready
+            .push_back(self.slots.len() - 1);
+        Machine(self.slots.len() - 1)
+    }
+
+    /// Send every output of `from` to the inputs of `to`, in addition to any previous link.
+    pub fn connect(&mut self, from: Machine, to: Machine) {
+        self.links[from.0].push(to.0);
+    }
+
+    /// Queue `value` as an input of `machine`.
+    pub fn push(&mut self, machine: Machine, value: i64) {
+        self.slots[machine.0]
+            .get_mut()
+            .unwrap()
+            .inbox
+            .push_back(value);
+    }
+
+    /// Every value output by `machine` so far, sent to its links or not.
+    pub fn outputs(&self, machine: Machine) -> Vec<i64> {
+        self.slots[machine.0].lock().unwrap().outputs.clone()
+    }
+
+    pub fn is_halted(&self, machine: Machine) -> bool {
+        self.slots[machine.0].lock().unwrap().halted
+    }
+
+    pub fn into_vms(self) -> Vec<VM> {
+        self.slots
+            .into_iter()
+            .map(|slot| slot.into_inner().unwrap().vm.unwrap())
+            .collect()
+    }
+
+    /// Run until every machine halted or waits for an input nobody will send.
+    ///
+    /// Stops at the first error, giving the machine that failed. That machine stays on the
+    /// failed instruction and is never run again, the others can be connected, fed and run
+    /// again afterward.
+    pub fn run(&mut self) -> Result<(), (Machine, VMError)> {
+        {
+            let queue = self.queue.get_mut().unwrap();
+            for (i, slot) in self.slots.iter_mut().enumerate() {
+                let slot = slot.get_mut().unwrap();
+                if slot.parked && !slot.inbox.is_empty() {
+                    slot.parked = false;
+                    queue.ready.push_back(i);
+                }
+            }
+        }
+        if self.workers == 1 {
+            self.work();
+        } else {
+            thread::scope(|scope| {
+                for _ in 0..self.workers {
+                    scope.spawn(|| self.work());
+                }
+            });
+        }
+        match self.queue.get_mut().unwrap().error.take() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    fn work(&self) {
+        while let Some(i) = self.next() {
+            let vm = self.slots[i].lock().unwrap().vm.take();
+            let result = self.slice(i, vm.expect("a ready machine has its VM"));
+            let mut queue = self.queue.lock().unwrap();
+            queue.running -= 1;
+            if let Err(error) = result {
+                queue.error.get_or_insert((Machine(i), error));
+            }
+            self.wake.notify_all();
+        }
+    }
+
+    /// The next ready machine, `None` once nothing can run anymore.
+    fn next(&self) -> Option<usize> {
+        let mut queue = self.queue.lock().unwrap();
+        loop {
+            if queue.error.is_some() {
+                return None;
+            }
+            if let Some(i) = queue.ready.pop_front() {
+                queue.running += 1;
+                return Some(i);
+            }
+            if queue.running == 0 {
+                return None;
+            }
+            queue = self.wake.wait(queue).unwrap();
+        }
+    }
+
+    /// Run machine `i` until it parks, halts or used its quantum, then give its VM back.
+    fn slice(&self, i: usize, mut vm: VM) -> Result<(), VMError> {
+        let start = vm.cycles;
+        let result = loop {
+            if vm.cycles - start >= QUANTUM {
+                self.slots[i].lock().unwrap().vm = Some(vm);
+                self.ready(i);
+                return Ok(());
+            }
+            match vm.tick() {
+                Ok(State::Continue) => {}
+                Ok(State::Output(o)) => {
+                    self.slots[i].lock().unwrap().outputs.push(o);
+                    for &to in &self.links[i] {
+                        self.send(to, o);
+                    }
+                }
+                Ok(State::Input) => {
+                    let mut slot = self.slots[i].lock().unwrap();
+                    if slot.inbox.is_empty() {
+                        slot.parked = true;
+                        slot.vm = Some(vm);
+                        return Ok(());
+                    }
+                    vm.inputs.extend(slot.inbox.drain(..));
+                }
+                Ok(State::Halt) => {
+                    let mut slot = self.slots[i].lock().unwrap();
+                    slot.halted = true;
+                    break Ok(());
+                }
+                Err(error) => break Err(error),
+            }
+        };
+        self.slots[i].lock().unwrap().vm = Some(vm);
+        result
+    }
+
+    fn send(&self, to: usize, value: i64) {
+        let mut slot = self.slots[to].lock().unwrap();
+        slot.inbox.push_back(value);
+        if slot.parked {
+            slot.parked = false;
+            drop(slot);
+            self.ready(to);
+        }
+    }
+
+    fn ready(&self, i: usize) {
+        self.queue.lock().unwrap().ready.push_back(i);
+        self.wake.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions;
+
+    fn amplifiers(workers: usize, phases: &[i64]) -> i64 {
+        let program = instructions(
+            "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
+        )
+        .unwrap()
+        .1;
+        let mut scheduler = Scheduler::new(workers);
+        let machines: Vec<_> = phases
+            .iter()
+            .map(|&phase| scheduler.spawn(VM::new(program.clone(), Some(phase))))
+            .collect();
+        for (i, &machine) in machines.iter().enumerate() {
+            scheduler.connect(machine, machines[(i + 1) % machines.len()]);
+        }
+        scheduler.push(machines[0], 0);
+        scheduler.run().unwrap();
+        assert!(machines.iter().all(|&machine| scheduler.is_halted(machine)));
+        *scheduler.outputs(machines[4]).last().unwrap()
+    }
+
+    #[test]
+    fn feedback_loop() {
+        assert_eq!(amplifiers(1, &[9, 8, 7, 6, 5]), 139629729);
+        assert_eq!(amplifiers(4, &[9, 8, 7, 6, 5]), 139629729);
+    }
+
+    #[test]
+    fn chain() {
+        // add one to every input
+        let program = instructions("3,11,1001,11,1,11,4,11,1105,1,0,0").unwrap().1;
+        let mut scheduler = Scheduler::new(8);
+        let machines: Vec<_> = (0..300)
+            .map(|_| scheduler.spawn(VM::new(program.clone(), None)))
+            .collect();
+        for pair in machines.windows(2) {
+            scheduler.connect(pair[0], pair[1]);
+        }
+        for value in 0..10 {
+            scheduler.push(machines[0], value);
+        }
+        scheduler.run().unwrap();
+        let last = scheduler.outputs(machines[299]);
+        assert_eq!(last, (300..310).collect::<Vec<_>>());
+        // the chain waits for more
+        assert!(!scheduler.is_halted(machines[0]));
+
+        scheduler.push(machines[0], 1000);
+        scheduler.run().unwrap();
+        assert_eq!(scheduler.outputs(machines[299]).last(), Some(&1300));
+    }
+
+    #[test]
+    fn error() {
+        let echo = instructions("3,9,4,9,1105,1,0,99,99,0").unwrap().1;
+        let mut scheduler = Scheduler::new(1);
+        let broken = instructions("3,3,77").unwrap().1;
+        let broken = scheduler.spawn(VM::new(broken, Some(42)));
+        let echo = scheduler.spawn(VM::new(echo, None));
+        scheduler.push(echo, 1);
+        assert_eq!(scheduler.run().unwrap_err().0, broken);
+
+        scheduler.push(broken, 2);
+        scheduler.push(echo, 3);
+        scheduler.run().unwrap();
+        assert_eq!(scheduler.outputs(echo), [1, 3]);
+        assert!(!scheduler.is_halted(broken));
+        assert_eq!(scheduler.into_vms()[0].p, 2);
+    }
+}
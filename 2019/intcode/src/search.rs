@@ -0,0 +1,463 @@
+//! Find inputs driving a program to an address or an output.
+//!
+//! The program is run concretely while every value computed from an input is also kept as an
+//! expression of the inputs. Each conditional jump on such a value is a constraint of the path
+//! taken, negating one of them and solving the path up to it gives the inputs of a new run. Only
+//! the instruction set of the 2019 puzzles is understood, and addresses are always concrete.
+//!
+//! A constraint is solved by changing a single input, the others keep the values of the run:
+//! constraints only satisfied by changing several inputs together are out of reach, unless
+//! the runs in between change them one at a time.
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use alloc::rc::Rc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Instruction, Opcodes, Operand, Param, State, VM};
+
+/// What the search tries to reach.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Goal {
+    /// Execute the instruction at this address.
+    Address(usize),
+    /// Output this value.
+    Output(i64),
+}
+
+#[derive(Debug)]
+enum Expr {
+    Const(i64),
+    Input(usize),
+    Add(Rc<Expr>, Rc<Expr>),
+    Mul(Rc<Expr>, Rc<Expr>),
+    Lt(Rc<Expr>, Rc<Expr>),
+    Eq(Rc<Expr>, Rc<Expr>),
+}
+
+/// Expressions bigger than this are replaced by their value, loops would grow them forever.
+const MAX_SIZE: usize = 64;
+/// A run stops after this many constraints, flipping each of them is tried.
+const MAX_PATH: usize = 64;
+
+impl Expr {
+    fn eval(&self, inputs: &[i64]) -> i64 {
+        match self {
+            Expr::Const(value) => *value,
+            Expr::Input(n) => inputs.get(*n).copied().unwrap_or(0),
+            Expr::Add(a, b) => a.eval(inputs).wrapping_add(b.eval(inputs)),
+            Expr::Mul(a, b) => a.eval(inputs).wrapping_mul(b.eval(inputs)),
+            Expr::Lt(a, b) => (a.eval(inputs) < b.eval(inputs)) as i64,
+            Expr::Eq(a, b) => (a.eval(inputs) == b.eval(inputs)) as i64,
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Expr::Const(_) | Expr::Input(_) => 1,
+            Expr::Add(a, b) | Expr::Mul(a, b) | Expr::Lt(a, b) | Expr::Eq(a, b) => {
+                1 + a.size() + b.size()
+            }
+        }
+    }
+
+    fn inputs(&self, found: &mut BTreeSet<usize>) {
+        match self {
+            Expr::Const(_) => {}
+            Expr::Input(n) => {
+                found.insert(*n);
+            }
+            Expr::Add(a, b) | Expr::Mul(a, b) | Expr::Lt(a, b) | Expr::Eq(a, b) => {
+                a.inputs(found);
+                b.inputs(found);
+            }
+        }
+    }
+}
+
+/// `expr` was non zero if `nonzero`.
+#[derive(Debug, Clone)]
+struct Constraint {
+    expr: Rc<Expr>,
+    nonzero: bool,
+    /// The program executed an instruction it computed from inputs, the cell and its value.
+    /// `expr` is their equality, the other values decoding to valid instructions are tried
+    /// instead of its negation.
+    instruction: Option<(Rc<Expr>, i64)>,
+}
+
+impl Constraint {
+    fn equal(expr: &Rc<Expr>, value: i64) -> Self {
+        Self {
+            expr: Rc::new(Expr::Eq(expr.clone(), Rc::new(Expr::Const(value)))),
+            nonzero: true,
+            instruction: None,
+        }
+    }
+
+    fn holds(&self, inputs: &[i64]) -> bool {
+        (self.expr.eval(inputs) != 0) == self.nonzero
+    }
+}
+
+/// The result of one concrete run.
+struct Run {
+    /// Inputs actually consumed.
+    inputs: Vec<i64>,
+    path: Vec<Constraint>,
+    reached: bool,
+    /// Outputs that could take other values, with the constraints of the path up to them.
+    outputs: Vec<(Rc<Expr>, usize)>,
+}
+
+/// Search for inputs reaching a `Goal`, see the module documentation.
+#[derive(Debug, Clone)]
+pub struct Search {
+    pub program: Vec<i64>,
+    /// Concrete runs tried before giving up.
+    pub max_runs: usize,
+    /// Instructions executed by a run before it is stopped.
+    pub max_cycles: u64,
+    /// Values tried around the current one for an input when a constraint isn't linear in it.
+    pub window: i64,
+}
+
+impl Search {
+    pub fn new(program: Vec<i64>) -> Self {
+        Self {
+            program,
+            max_runs: 1000,
+            max_cycles: 100_000,
+            window: 128,
+        }
+    }
+
+    /// Inputs making the program reach `goal`, a run missing inputs reads zeros.
+    pub fn find(&self, goal: Goal) -> Option<Vec<i64>> {
+        let mut todo = VecDeque::new();
+        todo.push_back((Vec::new(), 0));
+        let mut tried = BTreeSet::new();
+        let opcodes = Opcodes::default();
+        for _ in 0..self.max_runs {
+            let (inputs, bound) = todo.pop_front()?;
+            if !tried.insert(inputs.clone()) {
+                continue;
+            }
+            let run = self.run(&inputs, goal);
+            if run.reached {
+                return Some(run.inputs);
+            }
+            if let Goal::Output(value) = goal {
+                for (expr, len) in &run.outputs {
+                    let target = Constraint::equal(expr, value);
+                    if let Some(inputs) = self.solve(&run.path[..*len], &target, &run.inputs) {
+                        todo.push_front((inputs, run.path.len()));
+                    }
+                }
+            }
+            // generational search: only flip the branches after the one flipped to get here
+            for i in bound..run.path.len() {
+                let targets = match &run.path[i].instruction {
+                    Some((cell, value)) => (value.saturating_sub(self.window)
+                        ..=value.saturating_add(self.window))
+                        .filter(|other| other != value && opcodes.decode(&[*other], 0).is_ok())
+                        .map(|other| Constraint::equal(cell, other))
+                        .collect(),
+                    None => vec![Constraint {
+                        expr: run.path[i].expr.clone(),
+                        nonzero: !run.path[i].nonzero,
+                        instruction: None,
+                    }],
+                };
+                for target in targets {
+                    if let Some(inputs) = self.solve(&run.path[..i], &target, &run.inputs) {
+                        todo.push_back((inputs, i + 1));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn run(&self, inputs: &[i64], goal: Goal) -> Run {
+        let mut vm = VM::new(self.program.clone(), inputs.iter().copied());
+        let mut shadow: BTreeMap<usize, Rc<Expr>> = BTreeMap::new();
+        let mut run = Run {
+            inputs: inputs.to_vec(),
+            path: Vec::new(),
+            reached: false,
+            outputs: Vec::new(),
+        };
+        let mut consumed = 0;
+        while vm.cycles < self.max_cycles && run.path.len() < MAX_PATH {
+            if goal == Goal::Address(vm.p) {
+                run.reached = true;
+                break;
+            }
+            if let Some(cell) = shadow.get(&vm.p) {
+                let value = vm.mem.get(vm.p).copied().unwrap_or(0);
+                let mut constraint = Constraint::equal(cell, value);
+                constraint.instruction = Some((cell.clone(), value));
+                run.path.push(constraint);
+            }
+            let instruction = match vm.decode() {
+                Ok(instruction) => instruction,
+                Err(_) => break,
+            };
+            let values = symbolic(&vm, &shadow, &instruction);
+            let destination = instruction
+                .params
+                .iter()
+                .position(|param| *param == Param::Write)
+                .map(|i| address(&vm, &instruction, i));
+            let result = match (instruction.opcode, &values) {
+                (1, [Some(a), Some(b), _]) => Some(Rc::new(Expr::Add(a.clone(), b.clone()))),
+                (2, [Some(a), Some(b), _]) => Some(Rc::new(Expr::Mul(a.clone(), b.clone()))),
+                (7, [Some(a), Some(b), _]) => Some(Rc::new(Expr::Lt(a.clone(), b.clone()))),
+                (8, [Some(a), Some(b), _]) => Some(Rc::new(Expr::Eq(a.clone(), b.clone()))),
+                (3, _) => Some(Rc::new(Expr::Input(consumed))),
+                _ => None,
+            };
+            let state = match vm.tick() {
+                Ok(State::Input) => {
+                    run.inputs.push(0);
+                    vm.inputs.push_back(0);
+                    continue;
+                }
+                Ok(state) => state,
+                Err(_) => break,
+            };
+            if instruction.opcode == 3 {
+                consumed += 1;
+            }
+            if let Some(index) = destination {
+                match result.filter(|expr| {
+                    let mut found = BTreeSet::new();
+                    expr.inputs(&mut found);
+                    !found.is_empty() && expr.size() <= MAX_SIZE
+                }) {
+                    Some(expr) => shadow.insert(index, expr),
+                    None => shadow.remove(&index),
+                };
+            }
+            match (instruction.opcode, state, &values[0]) {
+                (5, _, Some(cond)) | (6, _, Some(cond)) if is_symbolic(cond) => {
+                    run.path.push(Constraint {
+                        expr: cond.clone(),
+                        nonzero: cond.eval(&run.inputs) != 0,
+                        instruction: None,
+                    })
+                }
+                (_, State::Output(value), Some(expr)) => {
+                    if goal == Goal::Output(value) {
+                        run.reached = true;
+                        break;
+                    }
+                    if is_symbolic(expr) {
+                        run.outputs.push((expr.clone(), run.path.len()));
+                    }
+                }
+                (_, State::Output(value), None) if goal == Goal::Output(value) => {
+                    run.reached = true;
+                    break;
+                }
+                (_, State::Halt, _) => break,
+                _ => {}
+            }
+        }
+        run.inputs.truncate(consumed);
+        run
+    }
+
+    /// Inputs satisfying `path` and `target`, changing a single input of `inputs`: each input
+    /// `target` depends on is tried in turn with the values of `guesses`.
+    fn solve(&self, path: &[Constraint], target: &Constraint, inputs: &[i64]) -> Option<Vec<i64>> {
+        let mut found = BTreeSet::new();
+        target.expr.inputs(&mut found);
+        for n in found {
+            let mut candidate = inputs.to_vec();
+            if candidate.len() < n + 1 {
+                candidate.resize(n + 1, 0);
+            }
+            let current = candidate[n];
+            for x in self.guesses(target, n, current, inputs) {
+                candidate[n] = x;
+                if target.holds(&candidate) && path.iter().all(|c| c.holds(&candidate)) {
+                    return Some(candidate);
+                }
+            }
+        }
+        None
+    }
+
+    /// Values of input `n` likely to satisfy `target`: where the difference of the compared
+    /// values crosses zero when it is linear in `n`, else the values up to `window` around
+    /// `current`. The values out of the range of `i64` are skipped.
+    fn guesses(&self, target: &Constraint, n: usize, current: i64, inputs: &[i64]) -> Vec<i64> {
+        let mut inputs = inputs.to_vec();
+        if inputs.len() < n + 1 {
+            inputs.resize(n + 1, 0);
+        }
+        // the input is solved for `lhs - rhs == wanted`
+        let (lhs, rhs, wanted): (&Expr, Option<&Expr>, i64) = match &*target.expr {
+            Expr::Eq(a, b) => (&**a, Some(&**b), if target.nonzero { 0 } else { 1 }),
+            Expr::Lt(a, b) => (&**a, Some(&**b), if target.nonzero { -1 } else { 0 }),
+            expr => (expr, None, if target.nonzero { 1 } else { 0 }),
+        };
+        let mut f = |x: i64| {
+            inputs[n] = x;
+            let rhs = rhs.map_or(0, |rhs| rhs.eval(&inputs));
+            lhs.eval(&inputs).wrapping_sub(rhs)
+        };
+        let (c, k) = (f(0), f(1).wrapping_sub(f(0)));
+        if f(-7) == c.wrapping_sub(k.wrapping_mul(7)) {
+            if k == 0 {
+                // the input doesn't matter
+                return Vec::new();
+            }
+            if let Some(x) = wanted.wrapping_sub(c).checked_div_euclid(k) {
+                return vec![Some(x), x.checked_add(1), x.checked_sub(1)]
+                    .into_iter()
+                    .flatten()
+                    .collect();
+            }
+        }
+        (1..=self.window)
+            .flat_map(|d| [current.checked_add(d), current.checked_sub(d)])
+            .flatten()
+            .collect()
+    }
+}
+
+fn is_symbolic(expr: &Expr) -> bool {
+    !matches!(expr, Expr::Const(_))
+}
+
+/// The cell parameter `i` refers to, immediates are the cell of the operand itself.
+fn address(vm: &VM, instruction: &Instruction, i: usize) -> usize {
+    match instruction.operands[i] {
+        Operand::Immediate(_) => instruction.address + 1 + i,
+        Operand::Position(index) => index as usize,
+        Operand::Relative(offset) => vm.r.wrapping_add(offset) as usize,
+    }
+}
+
+/// The expressions of the `Read` parameters of `instruction`, constants when they don't
+/// depend on inputs.
+fn symbolic(
+    vm: &VM,
+    shadow: &BTreeMap<usize, Rc<Expr>>,
+    instruction: &Instruction,
+) -> [Option<Rc<Expr>>; 3] {
+    let mut values = [None, None, None];
+    for (i, param) in instruction.params.iter().enumerate() {
+        if *param != Param::Read {
+            continue;
+        }
+        let address = address(vm, instruction, i);
+        values[i] = Some(match shadow.get(&address) {
+            Some(expr) => expr.clone(),
+            None => Rc::new(Expr::Const(vm.mem.get(address).copied().unwrap_or(0))),
+        });
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec;
+
+    #[test]
+    fn output() {
+        // output 3 * a + b + 5
+        let program = vec![
+            3, 20, 3, 21, 1002, 20, 3, 22, 1, 22, 21, 22, 1001, 22, 5, 22, 4, 22, 99,
+        ];
+        let inputs = Search::new(program.clone()).find(Goal::Output(50)).unwrap();
+        let mut vm = VM::new(program, inputs);
+        assert_eq!(vm.run().unwrap(), State::Output(50));
+    }
+
+    #[test]
+    fn address() {
+        // reach 20 only if a == 42 and b < a
+        let program = vec![
+            3, 30, 3, 31, 1008, 30, 42, 32, 1006, 32, 19, 7, 31, 30, 32, 1005, 32, 20, 99, 99, 104,
+            1, 99,
+        ];
+        let inputs = Search::new(program.clone())
+            .find(Goal::Address(20))
+            .unwrap();
+        assert_eq!(inputs[0], 42);
+        assert!(inputs[1] < 42);
+        let mut vm = VM::new(program, inputs);
+        assert_eq!(vm.run().unwrap(), State::Output(1));
+    }
+
+    #[test]
+    fn rewritten_instruction() {
+        // the input is added to the opcode at 6, like day5 does
+        let program = vec![3, 20, 1, 20, 6, 6, 100, 42, 99];
+        let mut search = Search::new(program);
+        // some of the instructions tried loop forever
+        search.max_cycles = 1000;
+        let inputs = search.find(Goal::Output(42)).unwrap();
+        assert_eq!(inputs, [4]);
+    }
+
+    #[test]
+    fn overflow() {
+        // reach 10 only if a == i64::MIN, the guesses around it are out of range
+        let program = vec![3, 20, 1008, 20, i64::MIN, 21, 1005, 21, 10, 99, 104, 1, 99];
+        let inputs = Search::new(program).find(Goal::Address(10)).unwrap();
+        assert_eq!(inputs, [i64::MIN]);
+
+        // reach 14 only if -a == i64::MIN, solving it divides i64::MIN by -1
+        let program = vec![
+            3,
+            20,
+            1002,
+            20,
+            -1,
+            22,
+            1008,
+            22,
+            i64::MIN,
+            21,
+            1005,
+            21,
+            14,
+            99,
+            104,
+            1,
+            99,
+        ];
+        let mut search = Search::new(program);
+        search.max_runs = 10;
+        assert_eq!(search.find(Goal::Address(14)), None);
+
+        // reach 16 only if a + 5 == i64::MIN + 2, the wrapped guess overflows the add
+        let program = vec![
+            3,
+            20,
+            1001,
+            20,
+            5,
+            21,
+            1008,
+            21,
+            i64::MIN + 2,
+            22,
+            1005,
+            22,
+            16,
+            99,
+            99,
+            99,
+            104,
+            1,
+            99,
+        ];
+        assert_eq!(Search::new(program).find(Goal::Address(16)), None);
+    }
+}
@@ -0,0 +1,102 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+/// A write into the code of the program.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CodeWrite {
+    /// Address of the instruction writing.
+    pub pc: usize,
+    pub address: usize,
+    /// The cell was already executed, else it is part of the instruction that follows.
+    pub executed: bool,
+}
+
+/// `00002: wrote 00006, the next instruction`
+impl fmt::Display for CodeWrite {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:05}: wrote {:05}, {}",
+            self.pc,
+            self.address,
+            if self.executed {
+                "already executed"
+            } else {
+                "the next instruction"
+            }
+        )
+    }
+}
+
+/// Watches the writes made through the `Write` parameters of the instructions for cells
+/// already executed or belonging to the instruction right after the writer.
+///
+/// Writes made by the host with `VM::put`, or by custom handlers to other addresses than their
+/// parameters, aren't watched.
+#[derive(Debug, Clone, Default)]
+pub struct SelfModification {
+    strict: bool,
+    executed: Vec<bool>,
+    writes: Vec<CodeWrite>,
+}
+
+impl SelfModification {
+    /// With `strict` a write into the code is an error.
+    pub fn new(len: usize, strict: bool) -> Self {
+        Self {
+            strict,
+            executed: vec![false; len],
+            writes: Vec::new(),
+        }
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    pub(crate) fn execute(&mut self, range: Range<usize>) {
+        if self.executed.len() < range.end {
+            self.executed.resize(range.end, false);
+        }
+        for executed in &mut self.executed[range] {
+            *executed = true;
+        }
+    }
+
+    /// Record the write of the instruction in `current` to `address` if it hits code, `next` are
+    /// the cells of the instruction that follows. Gives the write, in strict mode it is refused
+    /// and not recorded.
+    pub(crate) fn write(
+        &mut self,
+        current: Range<usize>,
+        address: usize,
+        next: Range<usize>,
+    ) -> Option<CodeWrite> {
+        // the instruction is only marked executed once it succeeded
+        let executed =
+            current.contains(&address) || self.executed.get(address).copied().unwrap_or(false);
+        if !executed && !next.contains(&address) {
+            return None;
+        }
+        let write = CodeWrite {
+            pc: current.start,
+            address,
+            executed,
+        };
+        if !self.strict {
+            self.writes.push(write);
+        }
+        Some(write)
+    }
+
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.writes.truncate(len);
+    }
+
+    /// Every write into the code so far, in order.
+    pub fn writes(&self) -> &[CodeWrite] {
+        &self.writes
+    }
+}
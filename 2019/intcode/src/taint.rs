@@ -0,0 +1,150 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{BitOr, BitOrAssign};
+
+/// The inputs a value depends on, input `n` is the `n`th value consumed since the analysis
+/// started.
+///
+/// Inputs from the 63rd on share the last label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Labels(u64);
+
+impl Labels {
+    const LAST: usize = 63;
+
+    pub fn input(n: usize) -> Self {
+        Self(1 << n.min(Self::LAST))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, n: usize) -> bool {
+        self.0 & Self::input(n).0 != 0
+    }
+
+    pub fn inputs(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..=Self::LAST).filter(move |&n| self.0 & (1 << n) != 0)
+    }
+}
+
+impl BitOr for Labels {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for Labels {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+/// `{0, 2}`
+impl fmt::Display for Labels {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, n) in self.inputs().enumerate() {
+            write!(f, "{}{}", if i == 0 { "" } else { ", " }, n)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// A conditional jump whose operands depended on inputs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Branch {
+    pub address: usize,
+    pub taken: bool,
+    pub labels: Labels,
+}
+
+/// Follows the inputs through the memory of the VM.
+///
+/// Every instruction gives the labels of all the cells it reads to the cells it writes, values
+/// consumed from the inputs add their own label. The cells read include the operands of the
+/// instruction, so a program rewriting its own code with an input is followed, but an address
+/// computed in `rb` isn't: a value picked from a table with `arb` isn't tainted by the index.
+#[derive(Debug, Clone, Default)]
+pub struct Taint {
+    cells: Vec<Labels>,
+    consumed: usize,
+    current: Labels,
+    written: Option<usize>,
+    outputs: Vec<Labels>,
+    branches: Vec<Branch>,
+}
+
+impl Taint {
+    pub fn new(len: usize) -> Self {
+        Self {
+            cells: vec![Labels::default(); len],
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn begin(&mut self) {
+        self.current = Labels::default();
+        self.written = None;
+    }
+
+    pub(crate) fn read(&mut self, index: usize) {
+        self.current |= self.cell(index);
+    }
+
+    pub(crate) fn write(&mut self, index: usize) {
+        if self.cells.len() < index + 1 {
+            self.cells.resize(index + 1, Labels::default());
+        }
+        self.cells[index] = self.current;
+        self.written = Some(index);
+    }
+
+    /// The instruction consumed `count` inputs, they taint what it wrote.
+    pub(crate) fn consume(&mut self, count: usize) {
+        for _ in 0..count {
+            self.current |= Labels::input(self.consumed);
+            self.consumed += 1;
+        }
+        if let Some(index) = self.written {
+            self.write(index);
+        }
+    }
+
+    pub(crate) fn output(&mut self) {
+        self.outputs.push(self.current);
+    }
+
+    pub(crate) fn branch(&mut self, address: usize, taken: bool) {
+        if !self.current.is_empty() {
+            self.branches.push(Branch {
+                address,
+                taken,
+                labels: self.current,
+            });
+        }
+    }
+
+    pub fn cell(&self, index: usize) -> Labels {
+        self.cells.get(index).copied().unwrap_or_default()
+    }
+
+    /// Number of inputs consumed so far.
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Labels of every value output so far, in order.
+    pub fn outputs(&self) -> &[Labels] {
+        &self.outputs
+    }
+
+    /// Every tainted conditional jump executed so far, in order.
+    pub fn branches(&self) -> &[Branch] {
+        &self.branches
+    }
+}
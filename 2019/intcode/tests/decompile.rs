@@ -0,0 +1,37 @@
+//! The pseudocode of the puzzle programs, compared with the one checked in `tests/fixtures`.
+
+use intcode::decompile::decompile;
+
+fn program(input: &str) -> Vec<i64> {
+    input
+        .trim()
+        .split(',')
+        .map(|value| value.parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn day5() {
+    let mut mem = program(include_str!("../../day5/input"));
+    // the first instruction adds the input to the opcode at 6, only known once it ran
+    assert_eq!(
+        decompile(&mem).to_string(),
+        include_str!("fixtures/day5.pseudocode")
+    );
+    mem[6] += 1;
+    assert_eq!(
+        decompile(&mem).to_string(),
+        include_str!("fixtures/day5-input-1.pseudocode")
+    );
+}
+
+#[test]
+fn day9() {
+    let mem = program(include_str!("../../day9/input"));
+    let pseudocode = decompile(&mem);
+    assert_eq!(pseudocode.functions().collect::<Vec<_>>(), [0, 922]);
+    assert_eq!(
+        pseudocode.to_string(),
+        include_str!("fixtures/day9.pseudocode")
+    );
+}
@@ -0,0 +1,81 @@
+//! Generates a test per example of every day, see `common::examples`.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The names of the `{name}.input` files of `dir`, sorted.
+fn examples(dir: &Path) -> Vec<String> {
+    let mut names: Vec<_> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "input")
+        })
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .collect();
+    names.sort();
+    names
+}
+
+/// The days of `year` as `(day, dir)`, sorted.
+fn days(year: &Path) -> Vec<(u32, PathBuf)> {
+    let mut days: Vec<_> = fs::read_dir(year)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let day = path
+                .file_name()?
+                .to_str()?
+                .strip_prefix("day")?
+                .parse()
+                .ok()?;
+            Some((day, path))
+        })
+        .collect();
+    days.sort();
+    days
+}
+
+fn main() {
+    let repository = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let mut years: Vec<(u32, PathBuf)> = fs::read_dir(&repository)
+        .expect("read the repository")
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let year = path.file_name()?.to_str()?.parse().ok()?;
+            Some((year, path))
+        })
+        .collect();
+    years.sort();
+
+    let mut tests = String::new();
+    for (year, dir) in years {
+        println!("cargo:rerun-if-changed={}", dir.display());
+        for (day, dir) in days(&dir) {
+            for name in examples(&dir.join("tests").join("fixtures")) {
+                let test: String = name
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                    .collect();
+                writeln!(
+                    tests,
+                    "#[test]\nfn y{year}_day{day}_{test}() {{\n    \
+                     common::examples::test::<aoc{year}_day{day}::Day{day}>({name:?});\n}}\n",
+                    year = year,
+                    day = day,
+                    test = test,
+                    name = name,
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    let out = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR")).join("examples.rs");
+    fs::write(out, tests).expect("write the tests");
+}
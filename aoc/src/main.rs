@@ -0,0 +1,304 @@
+use std::error::Error;
+use std::fs;
+use std::io::{ErrorKind, Read};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use common::bench::{Baseline, Options};
+use common::expected::{Expected, Verdict};
+use common::json::Json;
+use common::random::Rng;
+use common::{Format, Part, Puzzle};
+
+fn puzzles() -> Vec<Puzzle> {
+    vec![
+        Puzzle::new::<aoc2018_day1::Day1>(),
+        Puzzle::new::<aoc2018_day2::Day2>(),
+        Puzzle::new::<aoc2018_day3::Day3>(),
+        Puzzle::new::<aoc2018_day4::Day4>(),
+        Puzzle::new::<aoc2018_day5::Day5>(),
+        Puzzle::new::<aoc2018_day6::Day6>(),
+        Puzzle::new::<aoc2018_day7::Day7>(),
+        Puzzle::new::<aoc2019_day1::Day1>(),
+        Puzzle::new::<aoc2019_day2::Day2>(),
+        Puzzle::new::<aoc2019_day3::Day3>(),
+        Puzzle::new::<aoc2019_day4::Day4>(),
+        Puzzle::new::<aoc2019_day5::Day5>(),
+        Puzzle::new::<aoc2019_day6::Day6>(),
+        Puzzle::new::<aoc2019_day7::Day7>(),
+        Puzzle::new::<aoc2019_day8::Day8>(),
+        Puzzle::new::<aoc2019_day9::Day9>(),
+        Puzzle::new::<aoc2019_day10::Day10>(),
+    ]
+}
+
+const USAGE: &str =
+    "usage: aoc <year> [<day>] [--format human|json] | aoc all [--format human|json]
+       aoc verify [<year> [<day>]]
+       aoc bench [<year> [<day>]] [--warmup SECONDS] [--budget SECONDS] [--baseline FILE] [--save]
+                 [--generate SIZE [--seed N]]
+       aoc generate <year> <day> <size> [--seed N]";
+
+/// The puzzles selected by the arguments, every puzzle without any.
+fn select(args: &[String]) -> Result<Vec<Puzzle>, Box<dyn Error>> {
+    let (year, day) = match args {
+        [] => (None, None),
+        [all] if all == "all" => (None, None),
+        [year] => (Some(year.parse()?), None),
+        [year, day] => (Some(year.parse()?), Some(day.parse()?)),
+        _ => return Err(USAGE.into()),
+    };
+    let puzzles: Vec<_> = puzzles()
+        .into_iter()
+        .filter(|puzzle| year.is_none_or(|year| puzzle.year == year))
+        .filter(|puzzle| day.is_none_or(|day| puzzle.day == day))
+        .collect();
+    if puzzles.is_empty() {
+        return Err(format!("no puzzle for {}", args.join(" ")).into());
+    }
+    Ok(puzzles)
+}
+
+/// The checked-in input of the puzzle, else stdin when it's the only puzzle run.
+fn input(puzzle: &Puzzle, alone: bool) -> Result<Option<String>, Box<dyn Error>> {
+    match fs::read_to_string(puzzle.input()) {
+        Ok(input) => Ok(Some(input)),
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            if alone {
+                let mut input = String::new();
+                std::io::stdin().lock().read_to_string(&mut input)?;
+                Ok(Some(input))
+            } else {
+                Ok(None)
+            }
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Run the puzzles, in json a line per puzzle and the errors only in the objects.
+fn run(puzzles: &[Puzzle], format: Format) -> Result<(), Box<dyn Error>> {
+    let mut failed = 0;
+    for puzzle in puzzles {
+        if format == Format::Human {
+            println!("{} day {}", puzzle.year, puzzle.day);
+        }
+        let input = match input(puzzle, puzzles.len() == 1)? {
+            Some(input) => input,
+            None => {
+                match format {
+                    Format::Human => println!("No input"),
+                    Format::Json => {
+                        println!("{}", Json::failure(puzzle.year, puzzle.day, "no input"))
+                    }
+                }
+                continue;
+            }
+        };
+        let run = (puzzle.run)(&input);
+        match format {
+            Format::Human => println!("{}", run),
+            Format::Json => println!("{}", Json::run(puzzle.year, puzzle.day, &run)),
+        }
+        if let Some(e) = run.error() {
+            if format == Format::Human {
+                eprintln!("Error: {}", e);
+            }
+            failed += 1;
+        }
+    }
+
+    if failed != 0 {
+        return Err(format!("{} puzzles failed", failed).into());
+    }
+    Ok(())
+}
+
+/// Check the answers of every puzzle against the accepted ones, a puzzle without input or
+/// accepted answer is missing.
+fn verify(puzzles: &[Puzzle]) -> Result<(), Box<dyn Error>> {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    let mut expected = (0, Expected::default());
+    for puzzle in puzzles {
+        if expected.0 != puzzle.year {
+            expected = (puzzle.year, Expected::load(puzzle.year)?);
+        }
+        print!("{} day {:<2} ", puzzle.year, puzzle.day);
+        let input = match input(puzzle, false)? {
+            Some(input) => input,
+            None => {
+                println!("missing input");
+                missing += 1;
+                continue;
+            }
+        };
+        let run = (puzzle.run)(&input);
+        if let Some(e) = run.error() {
+            println!("error: {}", e);
+            failed += 1;
+            continue;
+        }
+        let verdicts: Vec<_> = Part::ALL
+            .iter()
+            .map(|&part| (part, expected.1.check(puzzle.day, part, &run.answers)))
+            .collect();
+        for (part, verdict) in &verdicts {
+            print!("{}: {}, ", part, verdict);
+        }
+        println!("{:?}", run.elapsed());
+        if verdicts
+            .iter()
+            .any(|(_, verdict)| matches!(verdict, Verdict::Fail { .. }))
+        {
+            failed += 1;
+        } else if verdicts
+            .iter()
+            .any(|(_, verdict)| *verdict == Verdict::Missing)
+        {
+            missing += 1;
+        } else {
+            passed += 1;
+        }
+    }
+
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    if failed != 0 {
+        return Err(format!("{} puzzles failed", failed).into());
+    }
+    Ok(())
+}
+
+/// Measure every phase of the puzzles, with the change from the baseline when it has the phase.
+///
+/// The baseline is `target/aoc-baseline` unless given, `--save` replaces it with the medians
+/// of this run. `--generate` benches random inputs of a size instead of the checked-in ones,
+/// against their own baseline.
+fn bench(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut options = Options::default();
+    let mut path = None;
+    let mut save = false;
+    let mut generate = None;
+    let mut seed = 0;
+    let mut select_args = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--warmup" => options.warmup = Duration::try_from_secs_f64(value()?.parse()?)?,
+            "--budget" => options.budget = Duration::try_from_secs_f64(value()?.parse()?)?,
+            "--baseline" => path = Some(PathBuf::from(value()?)),
+            "--save" => save = true,
+            "--generate" => generate = Some(value()?.parse()?),
+            "--seed" => seed = value()?.parse()?,
+            _ => select_args.push(arg.clone()),
+        }
+    }
+    let puzzles = select(&select_args)?;
+    let path = path.unwrap_or_else(|| {
+        let name = match generate {
+            Some(size) => format!("aoc-baseline-{}-{}", size, seed),
+            None => "aoc-baseline".to_string(),
+        };
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../target")
+            .join(name)
+    });
+
+    let mut baseline = Baseline::load(&path)?;
+    let mut failed = 0;
+    for puzzle in &puzzles {
+        println!("{} day {}", puzzle.year, puzzle.day);
+        let input = match generate {
+            Some(size) => (puzzle.generate)(&mut Rng::new(seed), size),
+            None => input(puzzle, false)?,
+        };
+        let input = match input {
+            Some(input) => input,
+            None if generate.is_some() => {
+                println!("No generator");
+                continue;
+            }
+            None => {
+                println!("No input");
+                continue;
+            }
+        };
+        let phases = match (puzzle.bench)(&input, &options) {
+            Ok(phases) => phases,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                failed += 1;
+                continue;
+            }
+        };
+        for (phase, stats) in phases {
+            print!("{}: {}", phase, stats);
+            if let Some(change) = baseline.change(puzzle.year, puzzle.day, phase, stats.median) {
+                print!(", {:+.1}%", change);
+            }
+            println!();
+            if save {
+                baseline.insert(puzzle.year, puzzle.day, phase, stats.median);
+            }
+        }
+    }
+
+    if save {
+        baseline.save(&path)?;
+    }
+    if failed != 0 {
+        return Err(format!("{} puzzles failed", failed).into());
+    }
+    Ok(())
+}
+
+/// Print a random input of the puzzle, the same for the same seed.
+fn generate(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (puzzle, size, seed) = match args {
+        [year, day, size, rest @ ..] => {
+            let seed = match rest {
+                [] => 0,
+                [flag, seed] if flag == "--seed" => seed.parse()?,
+                _ => return Err(USAGE.into()),
+            };
+            (select(&[year.clone(), day.clone()])?, size.parse()?, seed)
+        }
+        _ => return Err(USAGE.into()),
+    };
+    let puzzle = match puzzle.as_slice() {
+        [puzzle] => puzzle,
+        _ => return Err(USAGE.into()),
+    };
+    let input = (puzzle.generate)(&mut Rng::new(seed), size)
+        .ok_or_else(|| format!("{} day {} has no generator", puzzle.year, puzzle.day))?;
+    print!("{}", input);
+    Ok(())
+}
+
+/// Takes `--format` out of the arguments.
+fn format(args: &mut Vec<String>) -> Result<Option<Format>, Box<dyn Error>> {
+    let i = match args.iter().position(|arg| arg == "--format") {
+        Some(i) => i,
+        None => return Ok(None),
+    };
+    let format = args.get(i + 1).ok_or("--format needs a value")?.parse()?;
+    args.drain(i..i + 2);
+    Ok(Some(format))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<_> = std::env::args().skip(1).collect();
+    let format = format(&mut args)?;
+    match (args.split_first(), format) {
+        (Some((command, _)), Some(Format::Json))
+            if command == "verify" || command == "bench" || command == "generate" =>
+        {
+            Err(format!("{} has no json format", command).into())
+        }
+        (Some((command, args)), _) if command == "verify" => verify(&select(args)?),
+        (Some((command, args)), _) if command == "bench" => bench(args),
+        (Some((command, args)), _) if command == "generate" => generate(args),
+        (Some(_), format) => run(&select(&args)?, format.unwrap_or(Format::Human)),
+        (None, _) => Err(USAGE.into()),
+    }
+}
@@ -0,0 +1,62 @@
+//! The days used as libraries, through their public functions and not only `Solution`.
+
+use common::geometry::Vec2;
+use common::Solution;
+
+#[test]
+fn y2018_day4_stats() {
+    let input = include_str!("../../2018/day4/tests/fixtures/guards.input");
+    let events = aoc2018_day4::Day4::parse(input).unwrap();
+    let stats = aoc2018_day4::create_stats(&events).unwrap();
+    let (total, minutes) = &stats[&10];
+    assert_eq!(*total, 50);
+    assert_eq!(minutes[24], 2);
+    assert_eq!(stats[&99].0, 30);
+}
+
+#[test]
+fn y2018_day5_strip_polymer() {
+    let stripped = aoc2018_day5::strip_polymer("dabAcCaCBAcCcaDA".chars()).unwrap();
+    assert_eq!(stripped, "dabCBAcaDA");
+    assert!(aoc2018_day5::strip_polymer("aB1".chars()).is_err());
+}
+
+#[test]
+fn y2019_day3_intersections() {
+    let wires = aoc2019_day3::Day3::parse("R8,U5,L5,D3\nU7,R6,D4,L4\n").unwrap();
+    let mut crossings = aoc2019_day3::intersections(&wires);
+    crossings.sort_by_key(|point| point.step);
+    let crossings: Vec<_> = crossings
+        .iter()
+        .map(|point| (point.pos, point.step))
+        .collect();
+    assert_eq!(
+        crossings,
+        vec![(Vec2::new(6, -5), 30), (Vec2::new(3, -3), 40)]
+    );
+}
+
+#[test]
+fn y2019_day5_coverage() {
+    let input = include_str!("../../2019/day5/input");
+    let instructions = aoc2019_day5::Day5::parse(input).unwrap();
+    let report = aoc2019_day5::coverage(&instructions).unwrap();
+    let one = aoc2019_day5::Day5::part_one(&instructions).unwrap();
+    let two = aoc2019_day5::Day5::part_two(&instructions).unwrap();
+    assert_eq!((report.one.code, report.two.code), (one, two));
+    // part two tests the jumps and comparisons on top of part one
+    assert!(report.only_two() > 0);
+    assert!(report.one.coverage.executed().count() > 0);
+}
+
+#[test]
+fn y2019_day10_station() {
+    let input = include_str!("../../2019/day10/tests/fixtures/large.input");
+    let asteroids = aoc2019_day10::Day10::parse(input).unwrap();
+    let (station, detected) = aoc2019_day10::calc_one(&asteroids).unwrap();
+    assert_eq!((station, detected), (Vec2::new(11, 13), 210));
+    let order = aoc2019_day10::calc_two(&asteroids, station);
+    assert_eq!(order.len(), 299);
+    assert_eq!(order[0], Vec2::new(11, 12));
+    assert_eq!(order[199], Vec2::new(8, 2));
+}
@@ -0,0 +1,8 @@
+//! A test per example of the puzzle statements, generated by `build.rs` from the
+//! `tests/fixtures` directory of every day.
+//!
+//! An example is an input `{name}.input` with the expected answers `{name}.one` and
+//! `{name}.two`, or the expected parse error `{name}.error`, adding a file is enough for a new
+//! test.
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
@@ -0,0 +1,85 @@
+//! The solutions on random inputs, bigger than the examples and of every shape.
+//!
+//! The sizes stay small enough for a debug build, `aoc bench --generate SIZE` goes further.
+
+use common::random::Rng;
+use common::Solution;
+
+const SEEDS: u64 = 4;
+
+/// Solve inputs of `size` generated from a few seeds, every phase must succeed and give the
+/// same answers twice.
+fn stress<S: Solution>(size: usize) {
+    for seed in 0..SEEDS {
+        let input = S::generate(&mut Rng::new(seed), size)
+            .unwrap_or_else(|| panic!("{} day {} has no generator", S::YEAR, S::DAY));
+        assert_eq!(
+            S::generate(&mut Rng::new(seed), size).as_ref(),
+            Some(&input),
+            "{} day {} seed {}: not reproducible",
+            S::YEAR,
+            S::DAY,
+            seed
+        );
+        let run = common::run::<S>(&input);
+        if let Some(e) = run.error() {
+            panic!("{} day {} seed {}: {}", S::YEAR, S::DAY, seed, e);
+        }
+        let again = common::run::<S>(&input);
+        assert_eq!(
+            again.answers,
+            run.answers,
+            "{} day {} seed {}: the answers change between runs",
+            S::YEAR,
+            S::DAY,
+            seed
+        );
+    }
+}
+
+#[test]
+fn y2018_day4() {
+    stress::<aoc2018_day4::Day4>(2000);
+}
+
+#[test]
+fn y2018_day5() {
+    stress::<aoc2018_day5::Day5>(20_000);
+    for seed in 0..SEEDS {
+        let input = aoc2018_day5::Day5::generate(&mut Rng::new(seed), 1001).unwrap();
+        let polymer = aoc2018_day5::Day5::parse(&input).unwrap();
+        let one = aoc2018_day5::Day5::part_one(&polymer).unwrap().0[0];
+        let two = aoc2018_day5::Day5::part_two(&polymer).unwrap().0[0];
+        // the units annihilate by pairs
+        assert_eq!(one % 2, 1);
+        assert!(two <= one);
+    }
+}
+
+#[test]
+fn y2018_day6() {
+    stress::<aoc2018_day6::Day6>(30);
+}
+
+#[test]
+fn y2018_day7() {
+    stress::<aoc2018_day7::Day7>(26);
+    stress::<aoc2018_day7::Day7>(1);
+}
+
+#[test]
+fn y2019_day3() {
+    stress::<aoc2019_day3::Day3>(300);
+    stress::<aoc2019_day3::Day3>(2);
+}
+
+#[test]
+fn y2019_day6() {
+    stress::<aoc2019_day6::Day6>(20_000);
+}
+
+#[test]
+fn y2019_day10() {
+    stress::<aoc2019_day10::Day10>(250);
+    stress::<aoc2019_day10::Day10>(1);
+}
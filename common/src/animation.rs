@@ -0,0 +1,135 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::image::Rgb;
+
+/// The terminal itself, stdin is the input.
+#[cfg(unix)]
+const TERMINAL: Option<&str> = Some("/dev/tty");
+#[cfg(windows)]
+const TERMINAL: Option<&str> = Some("CONIN$");
+#[cfg(not(any(unix, windows)))]
+const TERMINAL: Option<&str> = None;
+
+/// Frames drawn over each other in the terminal with ANSI escapes, `--animate` of a day.
+///
+/// The input is read from stdin, so stepping through the frames waits for Enter on the
+/// terminal itself. The first error of the terminal stops the animation, `finish` returns it:
+/// the algorithms drawing the frames stay infallible. Past the maximum number of frames the
+/// others are dropped, only the last one is drawn by `finish`.
+pub struct Animation {
+    delay: Duration,
+    step: Option<BufReader<File>>,
+    max_frames: usize,
+    frames: usize,
+    /// The last frame dropped, drawn by `finish`.
+    dropped: Option<String>,
+    error: Option<io::Error>,
+}
+
+impl Animation {
+    /// `fps` frames per second, or a frame per Enter with `step`, `max_frames` at most.
+    pub fn new(fps: f64, step: bool, max_frames: usize) -> Result<Self, Box<dyn Error>> {
+        if !(fps.is_finite() && fps > 0.) {
+            return Err(format!("{} frames per second, expected more than 0", fps).into());
+        }
+        if max_frames == 0 {
+            return Err("0 frames at most, expected at least 1".into());
+        }
+        let delay = Duration::try_from_secs_f64(1. / fps)
+            .map_err(|_| format!("{} frames per second, a frame would last too long", fps))?;
+        let step = match (step, TERMINAL) {
+            (false, _) => None,
+            (true, Some(terminal)) => Some(BufReader::new(File::open(terminal)?)),
+            (true, None) => return Err("no terminal to step through the frames".into()),
+        };
+        Ok(Self {
+            delay,
+            step,
+            max_frames,
+            frames: 0,
+            dropped: None,
+            error: None,
+        })
+    }
+
+    /// Clear the screen and draw `frame`, then wait for the next one.
+    pub fn frame(&mut self, frame: impl fmt::Display) {
+        if self.error.is_some() {
+            return;
+        }
+        if self.frames == self.max_frames {
+            self.dropped = Some(frame.to_string());
+        } else if let Err(e) = self.draw(frame) {
+            self.error = Some(e);
+        }
+    }
+
+    fn draw(&mut self, frame: impl fmt::Display) -> io::Result<()> {
+        self.frames += 1;
+        let stdout = io::stdout();
+        let mut out = stdout.lock();
+        // cursor home, clear the screen
+        write!(out, "\x1b[H\x1b[2J{}", frame)?;
+        match &mut self.step {
+            Some(tty) => {
+                write!(out, "frame {}, Enter for the next", self.frames)?;
+                out.flush()?;
+                tty.read_line(&mut String::new())?;
+            }
+            None => {
+                out.flush()?;
+                thread::sleep(self.delay);
+            }
+        }
+        Ok(())
+    }
+
+    /// Draw the last frame if it was dropped. The number of frames drawn, or the error that
+    /// stopped the animation.
+    pub fn finish(mut self) -> io::Result<usize> {
+        if let (Some(frame), None) = (self.dropped.take(), &self.error) {
+            self.draw(frame)?;
+        }
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(self.frames),
+        }
+    }
+}
+
+/// `text` in a color, on the terminals with 24 bits colors.
+#[derive(Debug, Clone, Copy)]
+pub struct Paint<T>(pub T, pub Rgb);
+
+impl<T: fmt::Display> fmt::Display for Paint<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Paint(text, Rgb(r, g, b)) = self;
+        write!(f, "\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn animation() {
+        assert_eq!(
+            Paint('#', Rgb::RED).to_string(),
+            "\x1b[38;2;220;40;40m#\x1b[0m"
+        );
+        assert!(Animation::new(0., false, 100).is_err());
+        assert!(Animation::new(f64::NAN, false, 100).is_err());
+        assert!(Animation::new(1e-300, false, 100).is_err());
+        assert!(Animation::new(60., false, 0).is_err());
+        assert_eq!(
+            Animation::new(60., false, 100).unwrap().finish().unwrap(),
+            0
+        );
+    }
+}
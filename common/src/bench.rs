@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::counting::{self, Allocations};
+use crate::Solution;
+
+/// How long a phase is run.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Runs not measured before the measure, at least one.
+    pub warmup: Duration,
+    /// The phase is run again until the budget is spent.
+    pub budget: Duration,
+    /// Runs measured even when the budget is spent.
+    pub min_runs: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            warmup: Duration::from_millis(100),
+            budget: Duration::from_secs(1),
+            min_runs: 5,
+        }
+    }
+}
+
+/// The durations of the runs of a phase.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    /// Of a single run, only counted with the `count-alloc` feature.
+    pub allocations: Option<Allocations>,
+}
+
+impl Stats {
+    /// # Panics
+    ///
+    /// Panics if `samples` is empty.
+    pub fn new(samples: &mut [Duration]) -> Self {
+        assert!(!samples.is_empty(), "no sample");
+        samples.sort_unstable();
+        let runs = samples.len();
+        let median = if runs.is_multiple_of(2) {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        } else {
+            samples[runs / 2]
+        };
+        let mean = samples.iter().map(Duration::as_secs_f64).sum::<f64>() / runs as f64;
+        let variance = samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+        Self {
+            runs,
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            allocations: None,
+        }
+    }
+}
+
+/// `min 8.1µs, median 8.9µs, mean 9.2µs ± 700ns, 1043 runs`, then the allocations if counted.
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "min {:?}, median {:?}, mean {:?} ± {:?}, {} runs",
+            self.min, self.median, self.mean, self.stddev, self.runs
+        )?;
+        if let Some(allocations) = &self.allocations {
+            write!(f, ", {}", allocations)?;
+        }
+        Ok(())
+    }
+}
+
+/// The stats of every phase of a solution, in order.
+pub type Phases = Vec<(&'static str, Stats)>;
+
+/// Run `f` as told by `options`, gives the result of the last run. Stops at the first error.
+///
+/// The allocations are counted on the first run of the warmup.
+pub fn measure<T, E, F>(options: &Options, mut f: F) -> Result<(T, Stats), E>
+where
+    F: FnMut() -> Result<T, E>,
+{
+    let start = Instant::now();
+    let (res, allocations) = counting::count(|| black_box(f()));
+    let mut res = res?;
+    while start.elapsed() < options.warmup {
+        res = black_box(f()?);
+    }
+
+    let mut samples = Vec::new();
+    let start = Instant::now();
+    while samples.len() < options.min_runs || start.elapsed() < options.budget {
+        let run = Instant::now();
+        let next = black_box(f()?);
+        samples.push(run.elapsed());
+        // the previous result is dropped out of the measure
+        res = next;
+    }
+    let stats = Stats {
+        allocations,
+        ..Stats::new(&mut samples)
+    };
+    Ok((res, stats))
+}
+
+/// `bench` of a solution, without its type.
+pub type Bench = fn(&str, &Options) -> Result<Phases, Box<dyn Error>>;
+
+/// Measure every phase of a solution, they keep the names of `run`.
+pub fn bench<S: Solution>(input: &str, options: &Options) -> Result<Phases, Box<dyn Error>> {
+    let (input, parse) = measure(options, || S::parse(input))?;
+    let (_, one) = measure(options, || S::part_one(&input))?;
+    let (_, two) = measure(options, || S::part_two(&input))?;
+    Ok(vec![
+        ("parse_inputs", parse),
+        ("calc_one", one),
+        ("calc_two", two),
+    ])
+}
+
+/// The medians of a previous bench, to compare with.
+///
+/// Saved as lines of `year day phase nanoseconds`.
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    medians: HashMap<(u32, u32, String), Duration>,
+}
+
+impl Baseline {
+    /// An empty baseline if the file doesn't exist.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut medians = HashMap::new();
+        for (n, line) in text.lines().enumerate() {
+            let fields: Vec<_> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                [year, day, phase, nanos] => {
+                    medians.insert(
+                        (year.parse()?, day.parse()?, phase.to_string()),
+                        Duration::from_nanos(nanos.parse()?),
+                    );
+                }
+                [] => {}
+                _ => {
+                    return Err(
+                        format!("line {}: expected `year day phase nanoseconds`", n + 1).into(),
+                    )
+                }
+            }
+        }
+        Ok(Self { medians })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    pub fn get(&self, year: u32, day: u32, phase: &str) -> Option<Duration> {
+        self.medians.get(&(year, day, phase.to_string())).copied()
+    }
+
+    pub fn insert(&mut self, year: u32, day: u32, phase: &str, median: Duration) {
+        self.medians.insert((year, day, phase.to_string()), median);
+    }
+
+    /// Change of `median` from the baseline in percent.
+    pub fn change(&self, year: u32, day: u32, phase: &str, median: Duration) -> Option<f64> {
+        self.get(year, day, phase)
+            .map(|base| (median.as_secs_f64() - base.as_secs_f64()) / base.as_secs_f64() * 100.)
+    }
+}
+
+/// Sorted, so a saved baseline diffs well.
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut medians: Vec<_> = self.medians.iter().collect();
+        medians.sort();
+        for ((year, day, phase), median) in medians {
+            writeln!(f, "{} {} {} {}", year, day, phase, median.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let mut samples: Vec<_> = [4, 1, 3, 2]
+            .iter()
+            .map(|&n| Duration::from_micros(n))
+            .collect();
+        let stats = Stats::new(&mut samples);
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+        assert_eq!(stats.mean, Duration::from_nanos(2500));
+        // sqrt(1.25)µs
+        assert_eq!(stats.stddev.as_nanos(), 1118);
+    }
+
+    #[test]
+    fn baseline() {
+        let mut baseline = Baseline::default();
+        baseline.insert(2019, 10, "calc_one", Duration::from_micros(200));
+        baseline.insert(2018, 1, "parse_inputs", Duration::from_nanos(33));
+        let text = baseline.to_string();
+        assert_eq!(text, "2018 1 parse_inputs 33\n2019 10 calc_one 200000\n");
+
+        let baseline = Baseline::parse(&text).unwrap();
+        let change = baseline
+            .change(2019, 10, "calc_one", Duration::from_micros(150))
+            .unwrap();
+        assert!((change + 25.).abs() < 1e-9);
+        assert_eq!(
+            baseline.change(2019, 10, "calc_two", Duration::from_micros(150)),
+            None
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting the allocations made through it.
+///
+/// It is the global allocator with the `count-alloc` feature, else nothing is counted.
+pub struct Counting;
+
+impl Counting {
+    fn record(size: usize) {
+        COUNT.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    /// Counted as a new allocation of `new_size` bytes.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+            Self::record(new_size);
+        }
+        new
+    }
+}
+
+/// `true` if `Counting` is the global allocator.
+pub fn installed() -> bool {
+    cfg!(feature = "count-alloc")
+}
+
+/// The allocations made during a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Allocations {
+    pub count: usize,
+    pub bytes: usize,
+    /// The most bytes live at once, above those live before the run.
+    pub peak: usize,
+}
+
+/// `12 allocations, 3.1 KiB, peak 2.0 KiB`
+impl fmt::Display for Allocations {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {}, peak {}",
+            self.count,
+            Size(self.bytes),
+            Size(self.peak)
+        )
+    }
+}
+
+struct Size(usize);
+
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut size = self.0 as f64 / 1024.;
+        let mut unit = 0;
+        while size >= 1024. && unit + 1 < UNITS.len() {
+            size /= 1024.;
+            unit += 1;
+        }
+        write!(f, "{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Count the allocations made by `f`, `None` if `Counting` isn't the global allocator.
+///
+/// The counters are global, allocations of other threads running at the same time are
+/// counted too.
+pub fn count<T, F>(f: F) -> (T, Option<Allocations>)
+where
+    F: FnOnce() -> T,
+{
+    if !installed() {
+        return (f(), None);
+    }
+    let count = COUNT.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+    let res = f();
+    let allocations = Allocations {
+        count: COUNT.load(Ordering::Relaxed) - count,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    };
+    (res, Some(allocations))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let allocations = Allocations {
+            count: 12,
+            bytes: 3174,
+            peak: 512,
+        };
+        assert_eq!(
+            allocations.to_string(),
+            "12 allocations, 3.1 KiB, peak 512 B"
+        );
+        assert_eq!(Size(5 << 30).to_string(), "5.0 GiB");
+    }
+
+    #[cfg(feature = "count-alloc")]
+    #[test]
+    fn count() {
+        let (v, allocations) = super::count(|| {
+            let mut v = Vec::with_capacity(8);
+            v.extend(0u64..4);
+            let tmp = vec![0u8; 1000];
+            drop(tmp);
+            v
+        });
+        let allocations = allocations.unwrap();
+        assert!(allocations.count >= 2);
+        assert!(allocations.bytes >= 1064);
+        assert!(allocations.peak >= 1064);
+        assert_eq!(v.len(), 4);
+    }
+}
@@ -0,0 +1,142 @@
+use std::error::Error;
+use std::fmt;
+
+/// A parse error, where it is in the input and what was expected there.
+///
+/// ```text
+/// line 2, column 5: expected a digit
+/// U7,RR6,D4,L4
+///     ^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// From 1.
+    pub line: usize,
+    /// In characters, from 1.
+    pub column: usize,
+    /// The line of the error, without its newline.
+    pub text: String,
+    pub expected: String,
+}
+
+impl Diagnostic {
+    /// An error at the start of `at`, a slice of `input` like the rest of a parser.
+    ///
+    /// If `at` isn't in `input` it is taken as the end of it.
+    pub fn new(input: &str, at: &str, expected: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        let position = at.as_ptr() as usize;
+        let mut offset = if (start..=start + input.len()).contains(&position) {
+            position - start
+        } else {
+            input.len().saturating_sub(at.len())
+        };
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        Self {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// An error of a nom 5 parser run on `input`.
+    #[cfg(feature = "nom")]
+    pub fn nom(input: &str, e: nom::Err<(&str, nom::error::ErrorKind)>) -> Self {
+        match e {
+            nom::Err::Error((at, kind)) | nom::Err::Failure((at, kind)) => {
+                Self::new(input, at, expected(kind.description()))
+            }
+            nom::Err::Incomplete(_) => Self::new(input, &input[input.len()..], "more input"),
+        }
+    }
+
+    /// An error of a nom 4 parser run on `input`.
+    #[cfg(feature = "nom4")]
+    pub fn nom4(input: &str, e: nom4::Err<nom4::types::CompleteStr, u32>) -> Self {
+        match e {
+            nom4::Err::Error(context) | nom4::Err::Failure(context) => match context {
+                nom4::Context::Code(at, kind) => {
+                    Self::new(input, at.0, expected(kind.description()))
+                }
+            },
+            nom4::Err::Incomplete(_) => Self::new(input, &input[input.len()..], "more input"),
+        }
+    }
+}
+
+/// What a nom parser expects from the description of its error kind.
+#[cfg(any(feature = "nom", feature = "nom4"))]
+fn expected(kind: &str) -> String {
+    match kind {
+        "Tag" => "a tag",
+        "Char" => "a character",
+        "Digit" => "a digit",
+        "Alpha" | "Alphabetic" => "a letter",
+        "AlphaNumeric" => "a letter or a digit",
+        "Space" | "MultiSpace" => "a whitespace",
+        "CrLf" | "Eol" => "the end of the line",
+        "Eof" | "End of file" => "the end of the input",
+        "MapRes" | "Map on Result" => "a valid value",
+        "Alt" | "Alternative" => "one of the alternatives",
+        "Many1" | "Many1Count" => "at least one item",
+        "SeparatedList" | "Separated list" => "a list",
+        _ => return kind.to_lowercase(),
+    }
+    .to_string()
+}
+
+/// `line 2, column 5: expected a digit`, then the line with a caret under the column.
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        writeln!(f, "{}", self.text)?;
+        // keep the tabs so the caret lines up
+        let pad: String = self
+            .text
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        write!(f, "{}^", pad)
+    }
+}
+
+impl Error for Diagnostic {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagnostic() {
+        let input = "R8,U5\nU7,RR6,D4\n";
+        let at = &input[input.find("R6").unwrap()..];
+        let diagnostic = Diagnostic::new(input, at, "a digit");
+        assert_eq!((diagnostic.line, diagnostic.column), (2, 5));
+        assert_eq!(
+            diagnostic.to_string(),
+            "line 2, column 5: expected a digit\nU7,RR6,D4\n    ^"
+        );
+
+        let diagnostic = Diagnostic::new("é\tx", "x", "y");
+        assert_eq!((diagnostic.line, diagnostic.column), (1, 3));
+        assert!(diagnostic.to_string().ends_with("é\tx\n \t^"));
+
+        let diagnostic = Diagnostic::new("12\n", "", "a digit");
+        assert_eq!((diagnostic.line, diagnostic.column), (2, 1));
+    }
+}
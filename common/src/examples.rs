@@ -0,0 +1,113 @@
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use crate::{repository, Answers, Part, Solution};
+
+/// An example of a puzzle statement, from `{year}/day{day}/tests/fixtures` in the repository.
+///
+/// The input is `{name}.input` and the expected answer of each part `{name}.one` and
+/// `{name}.two`, without the last newline. A part without file isn't checked, examples often
+/// are for one part only. An invalid input has instead the error of its parse in
+/// `{name}.error`.
+#[derive(Debug, Clone)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub answers: Answers,
+    pub error: Option<String>,
+}
+
+impl Example {
+    pub fn dir(year: u32, day: u32) -> PathBuf {
+        repository()
+            .join(year.to_string())
+            .join(format!("day{}", day))
+            .join("tests")
+            .join("fixtures")
+    }
+
+    pub fn load(year: u32, day: u32, name: &str) -> Result<Self, Box<dyn Error>> {
+        let dir = Self::dir(year, day);
+        let input = fs::read_to_string(dir.join(format!("{}.input", name)))?;
+        let expected = |extension: &str| -> Result<Option<String>, Box<dyn Error>> {
+            let path = dir.join(format!("{}.{}", name, extension));
+            match fs::read_to_string(path) {
+                Ok(answer) => Ok(Some(strip_newline(answer))),
+                Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        };
+        Ok(Self {
+            name: name.to_string(),
+            input,
+            answers: Answers {
+                one: expected("one")?,
+                two: expected("two")?,
+            },
+            error: expected("error")?,
+        })
+    }
+
+    /// Solve the parts with an expected answer, an error tells the parts that differ. With an
+    /// expected error the parse must fail with it.
+    pub fn check<S: Solution>(&self) -> Result<(), Box<dyn Error>> {
+        if let Some(expected) = &self.error {
+            return match S::parse(&self.input) {
+                Ok(_) => {
+                    Err(format!("expected the error {:?}, the parse succeeded", expected).into())
+                }
+                Err(e) if e.to_string() == *expected => Ok(()),
+                Err(e) => {
+                    Err(
+                        format!("expected the error {:?}, got {:?}", expected, e.to_string())
+                            .into(),
+                    )
+                }
+            };
+        }
+        let input = S::parse(&self.input)?;
+        let mut wrong = Vec::new();
+        for &part in &Part::ALL {
+            let expected = match self.answers.get(part) {
+                Some(expected) => expected,
+                None => continue,
+            };
+            let got = match part {
+                Part::One => S::part_one(&input)?.to_string(),
+                Part::Two => S::part_two(&input)?.to_string(),
+            };
+            if got != expected {
+                wrong.push(format!(
+                    "part {}: expected {:?}, got {:?}",
+                    part, expected, got
+                ));
+            }
+        }
+        if !wrong.is_empty() {
+            return Err(wrong.join(", ").into());
+        }
+        Ok(())
+    }
+}
+
+fn strip_newline(mut answer: String) -> String {
+    if answer.ends_with('\n') {
+        answer.pop();
+    }
+    answer
+}
+
+/// Check an example, the body of the tests generated for every example.
+///
+/// # Panics
+///
+/// Panics if the example can't be loaded or an answer differs.
+pub fn test<S: Solution>(name: &str) {
+    let example = Example::load(S::YEAR, S::DAY, name)
+        .unwrap_or_else(|e| panic!("{} day {} {}: {}", S::YEAR, S::DAY, name, e));
+    if let Err(e) = example.check::<S>() {
+        panic!("{} day {} {}: {}", S::YEAR, S::DAY, name, e);
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use crate::{repository, Answers, Part};
+
+/// The outcome of an answer checked against the accepted one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        got: String,
+    },
+    /// No accepted answer is recorded.
+    Missing,
+}
+
+/// `fail (expected 2150, got 2151)`, newlines are escaped.
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected, got } => write!(
+                f,
+                "fail (expected {}, got {})",
+                escape(expected),
+                escape(got)
+            ),
+            Verdict::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(answer: &str) -> Result<String, Box<dyn Error>> {
+    let mut unescaped = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => unescaped.push('\n'),
+                Some('\\') => unescaped.push('\\'),
+                other => return Err(format!("unknown escape: \\{}", other.unwrap_or(' ')).into()),
+            }
+        } else {
+            unescaped.push(c);
+        }
+    }
+    Ok(unescaped)
+}
+
+/// The accepted answers for the checked-in inputs of a year, `{year}/answers` in the
+/// repository.
+///
+/// A line is `day part answer` with the part `one` or `two`, a newline in an answer is written
+/// `\n` and a backslash `\\`. Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct Expected {
+    answers: HashMap<(u32, Part), String>,
+}
+
+impl Expected {
+    pub fn path(year: u32) -> PathBuf {
+        repository().join(year.to_string()).join("answers")
+    }
+
+    /// The answers of `year`, none if the year has no file.
+    pub fn load(year: u32) -> Result<Self, Box<dyn Error>> {
+        match fs::read_to_string(Self::path(year)) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut answers = HashMap::new();
+        for (n, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(3, ' ');
+            let (day, part, answer) = match (fields.next(), fields.next(), fields.next()) {
+                (Some(day), Some(part), Some(answer)) => (day, part, answer),
+                _ => return Err(format!("line {}: expected `day part answer`", n + 1).into()),
+            };
+            let day = day
+                .parse()
+                .map_err(|e| format!("line {}: day {:?}: {}", n + 1, day, e))?;
+            let part = match part {
+                "one" => Part::One,
+                "two" => Part::Two,
+                _ => return Err(format!("line {}: unknown part {:?}", n + 1, part).into()),
+            };
+            if answers.insert((day, part), unescape(answer)?).is_some() {
+                return Err(format!("line {}: day {} part {} twice", n + 1, day, part).into());
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    /// A missing answer is checked as empty.
+    pub fn check(&self, day: u32, part: Part, answers: &Answers) -> Verdict {
+        let got = answers.get(part).unwrap_or_default();
+        match self.get(day, part) {
+            Some(expected) if expected == got => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+                got: got.to_string(),
+            },
+            None => Verdict::Missing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let expected =
+            Expected::parse("# 2019\n\n1 one 42\n1 two a b\n8 two #  #\\n # \\\\\n").unwrap();
+        assert_eq!(expected.get(1, Part::One), Some("42"));
+        assert_eq!(expected.get(1, Part::Two), Some("a b"));
+        assert_eq!(expected.get(8, Part::Two), Some("#  #\n # \\"));
+        assert_eq!(expected.get(8, Part::One), None);
+
+        assert!(Expected::parse("1 three 42").is_err());
+        assert!(Expected::parse("1 one 42\n1 one 43").is_err());
+        assert!(Expected::parse("1 one").is_err());
+    }
+}
@@ -0,0 +1,296 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use crate::grid::Pos;
+
+/// A signed vector, a point or a move on a plane.
+///
+/// `y` grows down like the rows of a grid, so `UP` is `(0, -1)` and turning right is clockwise
+/// on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Vec2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// The greatest common divisor, `gcd(0, 0)` is 0.
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2::new(0, 0);
+    pub const UP: Vec2 = Vec2::new(0, -1);
+    pub const RIGHT: Vec2 = Vec2::new(1, 0);
+    pub const DOWN: Vec2 = Vec2::new(0, 1);
+    pub const LEFT: Vec2 = Vec2::new(-1, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// `None` if a coordinate doesn't fit an `i64`.
+    pub fn from_pos((x, y): Pos) -> Option<Self> {
+        Some(Self::new(i64::try_from(x).ok()?, i64::try_from(y).ok()?))
+    }
+
+    /// The position in a grid, `None` if a coordinate is negative.
+    pub fn to_pos(self) -> Option<Pos> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+
+    /// Distance from the origin moving along the axes.
+    pub fn manhattan(self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    /// Distance from the origin moving along the axes and the diagonals.
+    pub fn chebyshev(self) -> u64 {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+
+    pub fn euclidean(self) -> f64 {
+        (self.x as f64).hypot(self.y as f64)
+    }
+
+    /// The greatest common divisor of the coordinates, the number of grid points on the
+    /// segment from the origin, the origin excluded.
+    pub fn gcd(self) -> u64 {
+        gcd(self.x.unsigned_abs(), self.y.unsigned_abs())
+    }
+
+    /// The smallest integer step in the same direction, `ZERO` stays `ZERO`.
+    pub fn direction(self) -> Self {
+        match self.gcd() {
+            0 => Self::ZERO,
+            // only a coordinate of i64::MIN with the other 0 has a gcd out of i64
+            gcd => i64::try_from(gcd).map_or(self.signum(), |gcd| self / gcd),
+        }
+    }
+
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    /// A quarter turn clockwise on screen, `UP` becomes `RIGHT`.
+    pub fn turn_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// A quarter turn counterclockwise on screen, `UP` becomes `LEFT`.
+    pub fn turn_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    pub fn dot(self, other: Self) -> i64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Positive if `other` is clockwise on screen from `self`, less than half a turn.
+    pub fn cross(self, other: Self) -> i64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Compare the angles clockwise on screen from `UP`, `ZERO` is before everything.
+    pub fn cmp_clockwise(&self, other: &Self) -> Ordering {
+        // 0 for the origin, 1 from up to right included, 2 from down to left included
+        fn half(v: &Vec2) -> u8 {
+            if *v == Vec2::ZERO {
+                0
+            } else if v.x > 0 || (v.x == 0 && v.y < 0) {
+                1
+            } else {
+                2
+            }
+        }
+        half(self)
+            .cmp(&half(other))
+            .then_with(|| 0.cmp(&self.cross(*other)))
+    }
+}
+
+/// `(x, y)`
+impl fmt::Display for Vec2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, n: i64) -> Self {
+        Self::new(self.x * n, self.y * n)
+    }
+}
+
+/// Rounds toward zero like the integers.
+impl Div<i64> for Vec2 {
+    type Output = Vec2;
+
+    fn div(self, n: i64) -> Self {
+        Self::new(self.x / n, self.y / n)
+    }
+}
+
+/// The smallest rectangle holding some points, both corners included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Bounds {
+    /// `None` without any point.
+    pub fn new<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Vec2>,
+    {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(
+            Self {
+                min: first,
+                max: first,
+            },
+            |bounds, point| Self {
+                min: Vec2::new(bounds.min.x.min(point.x), bounds.min.y.min(point.y)),
+                max: Vec2::new(bounds.max.x.max(point.x), bounds.max.y.max(point.y)),
+            },
+        ))
+    }
+
+    pub fn width(&self) -> u64 {
+        self.max.x.abs_diff(self.min.x) + 1
+    }
+
+    pub fn height(&self) -> u64 {
+        self.max.y.abs_diff(self.min.y) + 1
+    }
+
+    pub fn contains(&self, point: Vec2) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// The position of `point` in a grid of the bounds, `min` at `(0, 0)`.
+    pub fn to_pos(&self, point: Vec2) -> Option<Pos> {
+        if self.contains(point) {
+            (point - self.min).to_pos()
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vec2() {
+        let v = Vec2::new(-6, 4);
+        assert_eq!(v.manhattan(), 10);
+        assert_eq!(v.chebyshev(), 6);
+        assert_eq!(Vec2::new(3, -4).euclidean(), 5.);
+        assert_eq!(v.gcd(), 2);
+        assert_eq!(v.direction(), Vec2::new(-3, 2));
+        assert_eq!(Vec2::new(0, -7).direction(), Vec2::UP);
+        assert_eq!(Vec2::ZERO.direction(), Vec2::ZERO);
+        assert_eq!(Vec2::UP.turn_right(), Vec2::RIGHT);
+        assert_eq!(Vec2::UP.turn_left(), Vec2::LEFT);
+        assert_eq!(Vec2::LEFT.turn_right().turn_right(), Vec2::RIGHT);
+        assert_eq!(v.to_pos(), None);
+        assert_eq!(Vec2::new(2, 3).to_pos(), Some((2, 3)));
+        assert_eq!(Vec2::from_pos((2, 3)), Some(Vec2::new(2, 3)));
+        assert_eq!(Vec2::from_pos((usize::MAX, 0)), None);
+    }
+
+    #[test]
+    fn clockwise() {
+        let mut vs = vec![
+            Vec2::new(-1, -1),
+            Vec2::LEFT,
+            Vec2::new(-1, 1),
+            Vec2::DOWN,
+            Vec2::new(1, 1),
+            Vec2::RIGHT * 2,
+            Vec2::new(1, -1),
+            Vec2::UP,
+            Vec2::ZERO,
+        ];
+        vs.sort_by(Vec2::cmp_clockwise);
+        assert_eq!(
+            vs,
+            vec![
+                Vec2::ZERO,
+                Vec2::UP,
+                Vec2::new(1, -1),
+                Vec2::RIGHT * 2,
+                Vec2::new(1, 1),
+                Vec2::DOWN,
+                Vec2::new(-1, 1),
+                Vec2::LEFT,
+                Vec2::new(-1, -1),
+            ]
+        );
+        assert_eq!(
+            Vec2::RIGHT.cmp_clockwise(&(Vec2::RIGHT * 3)),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        let bounds =
+            Bounds::new(vec![Vec2::new(3, -1), Vec2::new(-2, 4), Vec2::new(0, 0)]).unwrap();
+        assert_eq!(bounds.min, Vec2::new(-2, -1));
+        assert_eq!(bounds.max, Vec2::new(3, 4));
+        assert_eq!((bounds.width(), bounds.height()), (6, 6));
+        assert_eq!(bounds.to_pos(Vec2::new(0, 0)), Some((2, 1)));
+        assert_eq!(bounds.to_pos(Vec2::new(4, 0)), None);
+        assert_eq!(Bounds::new(Vec::new()), None);
+    }
+}
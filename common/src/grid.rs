@@ -0,0 +1,325 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::diagnostic::Diagnostic;
+
+/// A position in a grid, `(x, y)` from the top left corner.
+pub type Pos = (usize, usize);
+
+/// A rectangle of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+const NEIGHBORS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const NEIGHBORS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cell: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![cell; width * height],
+        }
+    }
+
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+    where
+        F: FnMut(Pos) -> T,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// The cells row by row, `None` if they don't fill rows of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Option<Self> {
+        if width == 0 {
+            return if cells.is_empty() {
+                Some(Self {
+                    width,
+                    height: 0,
+                    cells,
+                })
+            } else {
+                None
+            };
+        }
+        if !cells.len().is_multiple_of(width) {
+            return None;
+        }
+        Some(Self {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    /// A map of characters, a row per line, with a cell per character given by `cell`.
+    ///
+    /// The lines are trimmed and the empty ones are skipped. `expected` tells the characters
+    /// `cell` knows, for the error of the others.
+    pub fn parse<F>(input: &str, expected: &str, mut cell: F) -> Result<Self, Diagnostic>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in input.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let len = cells.len();
+            for (i, c) in line.char_indices() {
+                let c = cell(c).ok_or_else(|| Diagnostic::new(input, &line[i..], expected))?;
+                cells.push(c);
+            }
+            let row = cells.len() - len;
+            match width {
+                None => width = Some(row),
+                Some(width) if width != row => {
+                    let at = &line[line.len()..];
+                    return Err(Diagnostic::new(
+                        input,
+                        at,
+                        format!("a row of {} cells like the first", width),
+                    ));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        if self.contains(pos) {
+            Some(pos.1 * self.width + pos.0)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(move |i| &mut self.cells[i])
+    }
+
+    /// The cells row by row.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut T> + ExactSizeIterator {
+        self.cells.iter_mut()
+    }
+
+    /// The positions row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// The cells row by row with their position.
+    pub fn cells(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        // `chunks_exact` panics on a width of 0
+        let width = self.width.max(1);
+        self.cells.chunks_exact(width).take(self.height)
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(x < self.width, "column {} out of {}", x, self.width);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The positions on the border, each once, clockwise from the top left corner.
+    pub fn border(&self) -> impl Iterator<Item = Pos> {
+        let (width, height) = (self.width, self.height);
+        let (right, bottom) = (width.saturating_sub(1), height.saturating_sub(1));
+        let top = (0..width).filter(move |_| height > 0).map(|x| (x, 0));
+        let inner = 1..bottom;
+        let right_side = inner
+            .clone()
+            .filter(move |_| width > 1)
+            .map(move |y| (right, y));
+        let bottom_side = (0..width)
+            .rev()
+            .filter(move |_| height > 1)
+            .map(move |x| (x, bottom));
+        let left_side = inner.rev().filter(move |_| width > 0).map(|y| (0, y));
+        top.chain(right_side).chain(bottom_side).chain(left_side)
+    }
+
+    fn offsets(
+        &self,
+        (x, y): Pos,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + '_ {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            if self.contains(pos) {
+                Some(pos)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// The positions up, right, down and left of `pos` in the grid.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.offsets(pos, &NEIGHBORS_4)
+    }
+
+    /// The positions around `pos` in the grid, diagonals included, clockwise from up.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.offsets(pos, &NEIGHBORS_8)
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+/// # Panics
+///
+/// Panics if `pos` is out of the grid, `get` doesn't.
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.index_of(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!("{:?} out of a grid of {}x{}", pos, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        match self.index_of(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("{:?} out of a grid of {}x{}", pos, self.width, self.height),
+        }
+    }
+}
+
+/// The cells of a row next to each other, a line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid() {
+        let mut grid = Grid::parse("#..\n.#.\n", "`#` or `.`", |c| match c {
+            '#' => Some(1),
+            '.' => Some(0),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 1)], 1);
+        assert_eq!(grid.get((3, 0)), None);
+        grid[(2, 1)] = 2;
+        assert_eq!(grid.row(1), &[0, 1, 2]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(grid.to_string(), "100\n012\n");
+
+        assert_eq!(
+            grid.border().collect::<Vec<_>>(),
+            vec![(0, 0), (1, 0), (2, 0), (2, 1), (1, 1), (0, 1)]
+        );
+        let grid = Grid::new(3, 3, ());
+        assert_eq!(grid.border().count(), 8);
+        assert_eq!(Grid::new(1, 3, ()).border().count(), 3);
+        assert_eq!(Grid::new(0, 0, ()).border().count(), 0);
+
+        assert_eq!(
+            grid.neighbors4((0, 1)).collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1), (0, 2)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8((2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let cell = |c| if c == '#' { Some(()) } else { None };
+        let e = Grid::parse("##\n#x\n", "`#`", cell).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 2));
+        let e = Grid::parse("##\n#\n", "`#`", cell).unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_str()),
+            (2, 2, "a row of 2 cells like the first")
+        );
+        assert_eq!(Grid::from_vec(2, vec![1, 2, 3]), None);
+    }
+}
@@ -0,0 +1,233 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::grid::Grid;
+
+/// A color of an exported image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GRAY: Rgb = Rgb(128, 128, 128);
+    pub const RED: Rgb = Rgb(220, 40, 40);
+
+    /// A color for the `i`th of many values, far from the colors of the values just before.
+    pub fn distinct(i: usize) -> Self {
+        // the golden ratio spreads the hues
+        let hue = (i as f64 * 0.618_033_988_75).fract();
+        Self::hsv(hue, 0.65, 0.95)
+    }
+
+    /// From `start` at 0 to `end` at 1.
+    pub fn gradient(start: Rgb, end: Rgb, t: f64) -> Self {
+        let t = t.clamp(0., 1.);
+        let mix = |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8;
+        Self(
+            mix(start.0, end.0),
+            mix(start.1, end.1),
+            mix(start.2, end.2),
+        )
+    }
+
+    /// `hue`, `saturation` and `value` from 0 to 1.
+    fn hsv(hue: f64, saturation: f64, value: f64) -> Self {
+        let h = hue * 6.;
+        let c = value * saturation;
+        let x = c * (1. - (h % 2. - 1.).abs());
+        let (r, g, b) = match h as u8 {
+            0 => (c, x, 0.),
+            1 => (x, c, 0.),
+            2 => (0., c, x),
+            3 => (0., x, c),
+            4 => (x, 0., c),
+            _ => (c, 0., x),
+        };
+        let m = value - c;
+        let channel = |v: f64| ((v + m) * 255.).round() as u8;
+        Self(channel(r), channel(g), channel(b))
+    }
+
+    /// The brightness, for the gray images.
+    pub fn luma(self) -> u8 {
+        ((299 * u32::from(self.0) + 587 * u32::from(self.1) + 114 * u32::from(self.2)) / 1000) as u8
+    }
+}
+
+/// The color of a value in an exported image.
+pub trait Palette {
+    fn color(&self) -> Rgb;
+}
+
+/// The file format of an exported image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Png,
+    /// Binary portable pixmap, in colors.
+    Ppm,
+    /// Binary portable graymap.
+    Pgm,
+}
+
+impl Format {
+    /// From the extension of the file.
+    pub fn from_path(path: &Path) -> Result<Self, Box<dyn Error>> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("png") => Ok(Format::Png),
+            Some(extension) if extension.eq_ignore_ascii_case("ppm") => Ok(Format::Ppm),
+            Some(extension) if extension.eq_ignore_ascii_case("pgm") => Ok(Format::Pgm),
+            _ => Err(format!(
+                "unknown image format of {}, expected .png, .ppm or .pgm",
+                path.display()
+            )
+            .into()),
+        }
+    }
+}
+
+/// Every cell is a square of `scale` pixels of side.
+pub fn encode(image: &Grid<Rgb>, format: Format, scale: usize) -> Vec<u8> {
+    let scale = scale.max(1);
+    let (width, height) = (image.width() * scale, image.height() * scale);
+    let rows = image.rows().flat_map(|row| std::iter::repeat_n(row, scale));
+    let pixels = |row: &[Rgb]| -> Vec<Rgb> {
+        row.iter()
+            .flat_map(|color| std::iter::repeat_n(*color, scale))
+            .collect()
+    };
+    match format {
+        Format::Ppm => {
+            let mut data = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+            for row in rows {
+                data.extend(pixels(row).iter().flat_map(|Rgb(r, g, b)| [*r, *g, *b]));
+            }
+            data
+        }
+        Format::Pgm => {
+            let mut data = format!("P5\n{} {}\n255\n", width, height).into_bytes();
+            for row in rows {
+                data.extend(pixels(row).iter().map(|color| color.luma()));
+            }
+            data
+        }
+        Format::Png => {
+            let mut raw = Vec::with_capacity(height * (1 + width * 3));
+            for row in rows {
+                // no filter
+                raw.push(0);
+                raw.extend(pixels(row).iter().flat_map(|Rgb(r, g, b)| [*r, *g, *b]));
+            }
+            png(width, height, &raw)
+        }
+    }
+}
+
+/// Write the image in the format of the extension of `path`.
+pub fn save(image: &Grid<Rgb>, path: &Path, scale: usize) -> Result<(), Box<dyn Error>> {
+    let format = Format::from_path(path)?;
+    fs::write(path, encode(image, format, scale))?;
+    Ok(())
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(&crc.to_be_bytes());
+}
+
+/// An 8 bits RGB PNG of the filtered rows `raw`, in a zlib stream of stored blocks: the
+/// images are small, they don't need a compression.
+fn png(width: usize, height: usize, raw: &[u8]) -> Vec<u8> {
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+
+    let mut header = Vec::with_capacity(13);
+    header.extend(&(width as u32).to_be_bytes());
+    header.extend(&(height as u32).to_be_bytes());
+    // bit depth, RGB, deflate, filters, no interlace
+    header.extend(&[8, 2, 0, 0, 0]);
+    chunk(&mut png, b"IHDR", &header);
+
+    let mut zlib = vec![0x78, 0x01];
+    let mut blocks = raw.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        zlib.extend(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        zlib.push(u8::from(last));
+        let len = block.len() as u16;
+        zlib.extend(&len.to_le_bytes());
+        zlib.extend(&(!len).to_le_bytes());
+        zlib.extend(block);
+    }
+    zlib.extend(&adler32(raw).to_be_bytes());
+    chunk(&mut png, b"IDAT", &zlib);
+
+    chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        let image = Grid::from_vec(2, vec![Rgb::BLACK, Rgb::WHITE]).unwrap();
+        assert_eq!(
+            super::encode(&image, Format::Pgm, 2),
+            b"P5\n4 2\n255\n\x00\x00\xff\xff\x00\x00\xff\xff".to_vec()
+        );
+        assert_eq!(
+            super::encode(&image, Format::Ppm, 1),
+            b"P6\n2 1\n255\n\x00\x00\x00\xff\xff\xff".to_vec()
+        );
+
+        let png = super::encode(&image, Format::Png, 1);
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR"));
+        assert!(png.ends_with(b"\x00\x00\x00\x00IEND\xae\x42\x60\x82"));
+        // the filter byte and both pixels, stored
+        assert!(png.windows(7).any(|w| w == b"\x00\x00\x00\x00\xff\xff\xff"));
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn colors() {
+        assert_eq!(Rgb::WHITE.luma(), 255);
+        assert_eq!(
+            Rgb::gradient(Rgb::BLACK, Rgb::WHITE, 0.5),
+            Rgb(128, 128, 128)
+        );
+        assert_ne!(Rgb::distinct(0), Rgb::distinct(1));
+    }
+}
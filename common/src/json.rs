@@ -0,0 +1,150 @@
+use std::fmt;
+
+use crate::{Part, Run};
+
+/// A run as a JSON object on a single line:
+///
+/// ```text
+/// {"year":2019,"day":10,"answers":{"one":"221","two":"806"},
+///  "phases":[{"name":"parse_inputs","nanos":11595,"allocations":null,"error":null},...],
+///  "error":null}
+/// ```
+///
+/// The answers are strings, `null` if the part failed or wasn't run. `allocations` is
+/// `{"count":88,"bytes":2662,"peak":1434}` with the `count-alloc` feature. `error` is the
+/// error of the first phase that failed.
+pub struct Json<'a> {
+    year: u32,
+    day: u32,
+    run: Option<&'a Run>,
+    error: Option<&'a str>,
+}
+
+impl<'a> Json<'a> {
+    pub fn run(year: u32, day: u32, run: &'a Run) -> Self {
+        Self {
+            year,
+            day,
+            run: Some(run),
+            error: run.error(),
+        }
+    }
+
+    /// A puzzle that couldn't run at all, like one without input, without answers nor phases.
+    pub fn failure(year: u32, day: u32, error: &'a str) -> Self {
+        Self {
+            year,
+            day,
+            run: None,
+            error: Some(error),
+        }
+    }
+}
+
+struct Str<'a>(Option<&'a str>);
+
+impl fmt::Display for Str<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self.0 {
+            Some(s) => s,
+            None => return write!(f, "null"),
+        };
+        write!(f, "\"")?;
+        for c in s.chars() {
+            match c {
+                '"' => write!(f, "\\\"")?,
+                '\\' => write!(f, "\\\\")?,
+                '\n' => write!(f, "\\n")?,
+                '\r' => write!(f, "\\r")?,
+                '\t' => write!(f, "\\t")?,
+                c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+                c => write!(f, "{}", c)?,
+            }
+        }
+        write!(f, "\"")
+    }
+}
+
+impl fmt::Display for Json<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{{\"year\":{},\"day\":{},\"answers\":",
+            self.year, self.day
+        )?;
+        match self.run {
+            Some(run) => {
+                write!(f, "{{")?;
+                for (i, part) in Part::ALL.iter().enumerate() {
+                    let sep = if i == 0 { "" } else { "," };
+                    let answer = Str(run.answers.get(*part));
+                    write!(f, "{}\"{}\":{}", sep, part, answer)?;
+                }
+                write!(f, "}}")?;
+            }
+            None => write!(f, "null")?,
+        }
+
+        write!(f, ",\"phases\":[")?;
+        for (i, phase) in self.run.iter().flat_map(|run| &run.phases).enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            write!(
+                f,
+                "{}{{\"name\":{},\"nanos\":{},\"allocations\":",
+                sep,
+                Str(Some(phase.name)),
+                phase.elapsed.as_nanos()
+            )?;
+            match &phase.allocations {
+                Some(allocations) => write!(
+                    f,
+                    "{{\"count\":{},\"bytes\":{},\"peak\":{}}}",
+                    allocations.count, allocations.bytes, allocations.peak
+                )?,
+                None => write!(f, "null")?,
+            }
+            write!(f, ",\"error\":{}}}", Str(phase.error.as_deref()))?;
+        }
+        write!(f, "],\"error\":{}}}", Str(self.error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Answers, Phase};
+    use std::time::Duration;
+
+    #[test]
+    fn json() {
+        let run = Run {
+            answers: Answers {
+                one: Some("8 \"8\"\n\t\u{1}".to_string()),
+                two: None,
+            },
+            printed: Answers::default(),
+            phases: vec![
+                Phase {
+                    name: "parse_inputs",
+                    elapsed: Duration::from_nanos(12),
+                    allocations: None,
+                    error: None,
+                },
+                Phase {
+                    name: "calc_two",
+                    elapsed: Duration::from_micros(1),
+                    allocations: None,
+                    error: Some("no \\o/".to_string()),
+                },
+            ],
+        };
+        assert_eq!(
+            Json::run(2019, 8, &run).to_string(),
+            r#"{"year":2019,"day":8,"answers":{"one":"8 \"8\"\n\t\u0001","two":null},"phases":[{"name":"parse_inputs","nanos":12,"allocations":null,"error":null},{"name":"calc_two","nanos":1000,"allocations":null,"error":"no \\o/"}],"error":"no \\o/"}"#
+        );
+        assert_eq!(
+            Json::failure(2018, 3, "no input").to_string(),
+            r#"{"year":2018,"day":3,"answers":null,"phases":[],"error":"no input"}"#
+        );
+    }
+}
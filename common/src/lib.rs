@@ -0,0 +1,347 @@
+use std::error::Error;
+use std::fmt;
+use std::io::Read;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use animation::Animation;
+use counting::Allocations;
+use grid::Grid;
+use image::Rgb;
+use random::Rng;
+
+pub mod animation;
+pub mod bench;
+pub mod counting;
+pub mod diagnostic;
+pub mod examples;
+pub mod expected;
+pub mod geometry;
+pub mod grid;
+pub mod image;
+pub mod json;
+pub mod random;
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOCATOR: counting::Counting = counting::Counting;
+
+/// A puzzle of a day, the input is parsed once and given to both parts.
+pub trait Solution {
+    const YEAR: u32;
+    const DAY: u32;
+
+    /// Can borrow the text of the input.
+    type Input<'a>;
+    /// Checked against the expected answers with `Display`, printed with `Debug` unless
+    /// `Display` spans lines.
+    type One: fmt::Display + fmt::Debug;
+    type Two: fmt::Display + fmt::Debug;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn Error>>;
+    fn part_one(input: &Self::Input<'_>) -> Result<Self::One, Box<dyn Error>>;
+    fn part_two(input: &Self::Input<'_>) -> Result<Self::Two, Box<dyn Error>>;
+
+    /// A picture of the solution, exported with `--image`. `None` for the days without.
+    fn image(_input: &Self::Input<'_>) -> Result<Option<Grid<Rgb>>, Box<dyn Error>> {
+        Ok(None)
+    }
+
+    /// Draw the solution in the terminal, with `--animate`. `false` for the days without.
+    fn animate(
+        _input: &Self::Input<'_>,
+        _animation: &mut Animation,
+    ) -> Result<bool, Box<dyn Error>> {
+        Ok(false)
+    }
+
+    /// A random valid input, for the stress tests and the benches at any size. `size` counts
+    /// what makes the input big, told by each day. `None` for the days without.
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+/// `one` or `two`
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "one"),
+            Part::Two => write!(f, "two"),
+        }
+    }
+}
+
+/// The answers of both parts, formatted. An answer is missing if its part failed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub one: Option<String>,
+    pub two: Option<String>,
+}
+
+impl Answers {
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.one.as_deref(),
+            Part::Two => self.two.as_deref(),
+        }
+    }
+}
+
+/// An answer spanning several lines, like an image, starts on its own line.
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let answers = [("One", &self.one), ("Two", &self.two)];
+        let answers = answers
+            .iter()
+            .filter_map(|(part, answer)| answer.as_ref().map(|answer| (part, answer)));
+        for (i, (part, answer)) in answers.enumerate() {
+            let sep = if answer.contains('\n') { "\n" } else { " " };
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "Answer {}:{}{}", part, sep, answer)?;
+        }
+        Ok(())
+    }
+}
+
+/// A phase of a run of a solution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Phase {
+    pub name: &'static str,
+    pub elapsed: Duration,
+    /// Only counted with the `count-alloc` feature.
+    pub allocations: Option<Allocations>,
+    pub error: Option<String>,
+}
+
+/// `parse_inputs: 1.2ms`, then the allocations if counted.
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {:?}", self.name, self.elapsed)?;
+        if let Some(allocations) = &self.allocations {
+            write!(f, ", {}", allocations)?;
+        }
+        Ok(())
+    }
+}
+
+/// The answers of a solution and its phases. The parts aren't run if the parse failed.
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub answers: Answers,
+    /// The answers as the binaries print them, see `Solution::One`.
+    pub printed: Answers,
+    pub phases: Vec<Phase>,
+}
+
+impl Run {
+    pub fn elapsed(&self) -> Duration {
+        self.phases.iter().map(|phase| phase.elapsed).sum()
+    }
+
+    /// The error of the first phase that failed.
+    pub fn error(&self) -> Option<&str> {
+        self.phases.iter().find_map(|phase| phase.error.as_deref())
+    }
+}
+
+/// Phases, then answers, like the binaries of the days always printed them.
+impl fmt::Display for Run {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for phase in &self.phases {
+            writeln!(f, "{}", phase)?;
+        }
+        write!(f, "{}", self.printed)
+    }
+}
+
+fn time<T, F>(phases: &mut Vec<Phase>, name: &'static str, f: F) -> Option<T>
+where
+    F: FnOnce() -> Result<T, Box<dyn Error>>,
+{
+    let start = Instant::now();
+    let (res, allocations) = counting::count(f);
+    let elapsed = start.elapsed();
+    let (res, error) = match res {
+        Ok(res) => (Some(res), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    phases.push(Phase {
+        name,
+        elapsed,
+        allocations,
+        error,
+    });
+    res
+}
+
+/// An answer with `Display`, then as printed.
+fn formats<T: fmt::Display + fmt::Debug>(answer: &T) -> (String, String) {
+    let display = answer.to_string();
+    let printed = if display.contains('\n') {
+        display.clone()
+    } else {
+        format!("{:?}", answer)
+    };
+    (display, printed)
+}
+
+/// Parse `input` and solve both parts, timing every phase. A part failing doesn't stop the
+/// other.
+pub fn run<S: Solution>(input: &str) -> Run {
+    let mut phases = Vec::new();
+    let mut answers = Answers::default();
+    let mut printed = Answers::default();
+    if let Some(input) = time(&mut phases, "parse_inputs", || S::parse(input)) {
+        let one = time(&mut phases, "calc_one", || {
+            S::part_one(&input).map(|one| formats(&one))
+        });
+        if let Some((answer, shown)) = one {
+            answers.one = Some(answer);
+            printed.one = Some(shown);
+        }
+        let two = time(&mut phases, "calc_two", || {
+            S::part_two(&input).map(|two| formats(&two))
+        });
+        if let Some((answer, shown)) = two {
+            answers.two = Some(answer);
+            printed.two = Some(shown);
+        }
+    }
+    Run {
+        answers,
+        printed,
+        phases,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Human,
+    /// One object per line, see `json`.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format {:?}, expected human or json", s)),
+        }
+    }
+}
+
+/// The root of the repository, the years are in it.
+fn repository() -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), ".."].iter().collect()
+}
+
+/// A `Solution` without its types, so the puzzles of every day can be listed together.
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u32,
+    pub run: fn(&str) -> Run,
+    pub bench: bench::Bench,
+    pub generate: fn(&mut Rng, usize) -> Option<String>,
+}
+
+impl Puzzle {
+    pub fn new<S: Solution>() -> Self {
+        Self {
+            year: S::YEAR,
+            day: S::DAY,
+            run: run::<S>,
+            bench: bench::bench::<S>,
+            generate: S::generate,
+        }
+    }
+
+    /// The checked-in input of the puzzle, `{year}/day{day}/input` in the repository.
+    pub fn input(&self) -> PathBuf {
+        repository()
+            .join(self.year.to_string())
+            .join(format!("day{}", self.day))
+            .join("input")
+    }
+}
+
+const USAGE: &str = "usage: [--format human|json] [--image FILE.png|ppm|pgm [--scale N]] \
+                     [--animate [--fps N] [--step] [--max-frames N]]";
+
+/// Solve the input given on stdin, the `main` of the binary of a day.
+///
+/// `--image` exports the picture of the solution, each cell a square of `--scale` pixels.
+/// `--animate` draws the solution in the terminal before the answers, `--fps` frames per second
+/// or a frame per Enter with `--step`, the frames past `--max-frames` are dropped.
+pub fn main<S: Solution>() -> Result<(), Box<dyn Error>> {
+    let mut format = Format::Human;
+    let mut image = None;
+    let mut scale = 1;
+    let mut animate = false;
+    let mut fps = 30.;
+    let mut step = false;
+    let mut max_frames = 600;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(USAGE);
+        match arg.as_str() {
+            "--format" => format = value()?.parse()?,
+            "--image" => image = Some(PathBuf::from(value()?)),
+            "--scale" => scale = value()?.parse()?,
+            "--animate" => animate = true,
+            "--fps" => fps = value()?.parse()?,
+            "--step" => step = true,
+            "--max-frames" => max_frames = value()?.parse()?,
+            _ => return Err(USAGE.into()),
+        }
+    }
+
+    let mut input = String::new();
+    std::io::stdin().lock().read_to_string(&mut input)?;
+    if animate {
+        if format == Format::Json {
+            return Err("--animate has no json format".into());
+        }
+        let mut animation = Animation::new(fps, step, max_frames)?;
+        if !S::animate(&S::parse(&input)?, &mut animation)? {
+            return Err(format!("{} day {} has no animation", S::YEAR, S::DAY).into());
+        }
+        animation.finish()?;
+    }
+    let run = run::<S>(&input);
+    match format {
+        Format::Human => println!("{}", run),
+        Format::Json => println!("{}", json::Json::run(S::YEAR, S::DAY, &run)),
+    }
+    if let Some(e) = run.error() {
+        // the error can span lines, like a `Diagnostic`, it isn't printed with `Debug`
+        if format == Format::Human {
+            eprintln!("Error: {}", e);
+        }
+        std::process::exit(1);
+    }
+
+    if let Some(path) = image {
+        let picture = S::image(&S::parse(&input)?)?
+            .ok_or_else(|| format!("{} day {} has no image", S::YEAR, S::DAY))?;
+        image::save(&picture, &path, scale)?;
+    }
+    Ok(())
+}